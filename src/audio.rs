@@ -0,0 +1,135 @@
+//! Sound effects for the desk and the sting when the newspaper arrives, and the office
+//! ambience while at work, each at the volume of its category in the settings.
+use bevy::{audio::AudioSink, prelude::*};
+
+use crate::{settings::Settings, PickedUp};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SoundCategory {
+    Effects,
+    Ambience,
+}
+
+impl SoundCategory {
+    pub const ALL: [SoundCategory; 2] = [SoundCategory::Effects, SoundCategory::Ambience];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SoundCategory::Effects => "Effects",
+            SoundCategory::Ambience => "Ambience",
+        }
+    }
+
+    pub fn volume(self, settings: &Settings) -> f32 {
+        match self {
+            SoundCategory::Effects => settings.volume.effects,
+            SoundCategory::Ambience => settings.volume.ambience,
+        }
+    }
+
+    pub fn volume_mut(self, settings: &mut Settings) -> &mut f32 {
+        match self {
+            SoundCategory::Effects => &mut settings.volume.effects,
+            SoundCategory::Ambience => &mut settings.volume.ambience,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Sound {
+    /// Paper being picked up
+    Rustle,
+    /// Something being put down on the desk
+    Drop,
+    /// A stamp leaving its mark
+    Stamp,
+    /// The newspaper arriving
+    Sting,
+}
+
+impl Sound {
+    fn path(self) -> &'static str {
+        match self {
+            Sound::Rustle => "sounds/rustle.wav",
+            Sound::Drop => "sounds/drop.wav",
+            Sound::Stamp => "sounds/stamp.wav",
+            Sound::Sting => "sounds/sting.wav",
+        }
+    }
+
+    fn category(self) -> SoundCategory {
+        match self {
+            Sound::Rustle | Sound::Drop | Sound::Stamp | Sound::Sting => SoundCategory::Effects,
+        }
+    }
+}
+
+/// Sent to play a sound once
+pub struct PlaySound(pub Sound);
+
+pub fn play_sounds(
+    mut sounds: EventReader<PlaySound>,
+    settings: Res<Settings>,
+    audio: Res<Audio>,
+    asset_server: Res<AssetServer>,
+) {
+    for &PlaySound(sound) in sounds.iter() {
+        audio.play_with_settings(
+            asset_server.load(sound.path()),
+            PlaybackSettings::ONCE.with_volume(sound.category().volume(&settings)),
+        );
+    }
+}
+
+pub fn rustle_picked_up(mut picked_up: EventReader<PickedUp>, mut sounds: EventWriter<PlaySound>) {
+    for _ in picked_up.iter() {
+        sounds.send(PlaySound(Sound::Rustle));
+    }
+}
+
+pub fn play_newspaper_sting(mut sounds: EventWriter<PlaySound>) {
+    sounds.send(PlaySound(Sound::Sting));
+}
+
+/// The office ambience loop playing while at the desk
+#[derive(Resource)]
+pub struct Ambience(Handle<AudioSink>);
+
+pub fn start_ambience(
+    settings: Res<Settings>,
+    audio: Res<Audio>,
+    sinks: Res<Assets<AudioSink>>,
+    asset_server: Res<AssetServer>,
+    mut commands: Commands,
+) {
+    let sink = audio.play_with_settings(
+        asset_server.load("sounds/office.wav"),
+        PlaybackSettings::LOOP.with_volume(SoundCategory::Ambience.volume(&settings)),
+    );
+    commands.insert_resource(Ambience(sinks.get_handle(sink)));
+}
+
+pub fn stop_ambience(
+    ambience: Option<Res<Ambience>>,
+    sinks: Res<Assets<AudioSink>>,
+    mut commands: Commands,
+) {
+    if let Some(sink) = ambience.and_then(|ambience| sinks.get(&ambience.0)) {
+        sink.stop();
+    }
+    commands.remove_resource::<Ambience>();
+}
+
+/// Turns the ambience up or down as soon as its volume setting changes
+pub fn update_ambience_volume(
+    settings: Res<Settings>,
+    ambience: Option<Res<Ambience>>,
+    sinks: Res<Assets<AudioSink>>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+    if let Some(sink) = ambience.and_then(|ambience| sinks.get(&ambience.0)) {
+        sink.set_volume(SoundCategory::Ambience.volume(&settings));
+    }
+}
@@ -0,0 +1,73 @@
+//! Keeping dragged things on the part of the desk that's in view.
+use bevy::{prelude::*, window::WindowResized};
+
+use crate::{BeingDragged, DragHitBox, MainCamera};
+
+/// The part of the desk in view, in world coordinates
+#[derive(Debug, Resource)]
+pub struct DeskBounds {
+    pub rect: Rect,
+    /// How much of something has to stay in view so it can still be grabbed
+    pub grab_margin: f32,
+}
+
+impl Default for DeskBounds {
+    fn default() -> Self {
+        DeskBounds {
+            rect: Rect::default(),
+            grab_margin: 40.0,
+        }
+    }
+}
+
+impl DeskBounds {
+    /// Moves something's center just far enough that enough of it stays in view
+    pub fn clamp(&self, position: Vec2, size: Vec2) -> Vec2 {
+        let margin = Vec2::splat(self.grab_margin).min(size / 2.0);
+        position.clamp(
+            self.rect.min - size / 2.0 + margin,
+            self.rect.max + size / 2.0 - margin,
+        )
+    }
+}
+
+/// Works out the bounds from the window and camera at startup and whenever the window is resized,
+/// pulling anything that ended up out of view back in
+pub fn update_desk_bounds(
+    mut resized: EventReader<WindowResized>,
+    windows: Res<Windows>,
+    camera: Query<(&OrthographicProjection, &Transform), With<MainCamera>>,
+    mut bounds: ResMut<DeskBounds>,
+    mut items: Query<(&DragHitBox, &mut Transform), Without<MainCamera>>,
+) {
+    if resized.iter().count() == 0 && !bounds.is_added() {
+        return;
+    }
+    let (Some(window), Ok((projection, camera_transform))) =
+        (windows.get_primary(), camera.get_single())
+    else {
+        return;
+    };
+
+    // The camera shows the window's size in world units, scaled by the projection
+    let view_size = Vec2::new(window.width(), window.height()) * projection.scale;
+    bounds.rect = Rect::from_center_size(camera_transform.translation.truncate(), view_size);
+
+    for (hitbox, mut transform) in &mut items {
+        let position = bounds.clamp(transform.translation.truncate(), hitbox.size);
+        transform.translation = position.extend(transform.translation.z);
+    }
+}
+
+/// Stops dragged things from leaving the desk
+pub fn keep_on_desk(
+    bounds: Res<DeskBounds>,
+    mut dragged: Query<(&DragHitBox, &mut Transform), With<BeingDragged>>,
+) {
+    for (hitbox, mut transform) in &mut dragged {
+        let position = bounds.clamp(transform.translation.truncate(), hitbox.size);
+        if position != transform.translation.truncate() {
+            transform.translation = position.extend(transform.translation.z);
+        }
+    }
+}
@@ -0,0 +1,298 @@
+//! The campaign: which dossiers land on the analyst's desk on which day.
+//!
+//! Written as `assets/main.campaign.ron`, listing the dossier files for each day in order
+//! along with the handbook the analyst works by.
+use std::collections::VecDeque;
+
+use bevy::{
+    asset::{AssetLoader, AssetPath, LoadContext, LoadedAsset},
+    prelude::*,
+    reflect::TypeUuid,
+    utils::BoxedFuture,
+};
+use serde::Deserialize;
+
+use crate::{decision::Recommendation, dossier::DossierAsset, handbook::Handbook, StampKind};
+
+#[derive(Debug, TypeUuid)]
+#[uuid = "71715fa5-ff2d-45f0-951c-c0ef17a1f0c7"]
+pub struct CampaignAsset {
+    pub handbook: Handbook,
+    pub days: Vec<Vec<Handle<DossierAsset>>>,
+}
+
+#[derive(Deserialize)]
+struct CampaignFile {
+    handbook: Handbook,
+    days: Vec<DayFile>,
+}
+
+#[derive(Deserialize)]
+struct DayFile {
+    /// Paths of the dossier files, relative to `assets/`.
+    dossiers: Vec<String>,
+}
+
+#[derive(Default)]
+pub struct CampaignLoader;
+
+impl AssetLoader for CampaignLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let file: CampaignFile = ron::de::from_bytes(bytes)?;
+            let mut dependencies = Vec::new();
+            let days = file
+                .days
+                .into_iter()
+                .map(|day| {
+                    day.dossiers
+                        .into_iter()
+                        .map(|path| {
+                            let path = AssetPath::from(path.as_str()).to_owned();
+                            let handle = load_context.get_handle(path.clone());
+                            dependencies.push(path);
+                            handle
+                        })
+                        .collect()
+                })
+                .collect();
+            load_context.set_default_asset(
+                LoadedAsset::new(CampaignAsset {
+                    handbook: file.handbook,
+                    days,
+                })
+                .with_dependencies(dependencies),
+            );
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["campaign.ron"]
+    }
+}
+
+/// Progress through the campaign.
+#[derive(Resource)]
+pub struct Campaign {
+    days: Vec<Day>,
+    today: usize,
+    /// How many recommendations the analyst has filed by the handbook
+    commendations: u32,
+    /// The recommendation stamped on the current dossier, before it's filed
+    stamped: Option<Recommendation>,
+    /// Whether the analyst was fired for filing a recommendation against the handbook
+    dismissed: bool,
+}
+
+pub struct Day {
+    /// Dossiers still waiting to be handled today, front first.
+    pub dossiers: VecDeque<Handle<DossierAsset>>,
+    /// What the analyst decided for the dossiers already filed today.
+    pub decisions: Vec<Decision>,
+}
+
+pub struct Decision {
+    pub dossier: Handle<DossierAsset>,
+    pub stamp: StampKind,
+    /// Where the stamp is on the dossier, relative to the paper.
+    pub position: Vec2,
+}
+
+impl Campaign {
+    pub fn new(asset: &CampaignAsset) -> Self {
+        Campaign {
+            days: asset
+                .days
+                .iter()
+                .map(|dossiers| Day {
+                    dossiers: dossiers.iter().cloned().collect(),
+                    decisions: Vec::new(),
+                })
+                .collect(),
+            today: 0,
+            commendations: 0,
+            stamped: None,
+            dismissed: false,
+        }
+    }
+
+    /// Picks a campaign back up from what was decided so far, or `None` if the decisions don't
+    /// fit the campaign.
+    pub fn restore(
+        asset: &CampaignAsset,
+        today: usize,
+        commendations: u32,
+        decisions: Vec<Vec<Decision>>,
+        stamped: Option<Recommendation>,
+    ) -> Option<Self> {
+        let mut campaign = Campaign::new(asset);
+        if today >= campaign.days.len() || decisions.len() > campaign.days.len() {
+            return None;
+        }
+        for (day, decisions) in campaign.days.iter_mut().zip(decisions) {
+            for decision in decisions {
+                if day.dossiers.pop_front()? != decision.dossier {
+                    return None;
+                }
+                day.decisions.push(decision);
+            }
+        }
+        campaign.today = today;
+        campaign.commendations = commendations;
+        campaign.stamped = stamped;
+        Some(campaign)
+    }
+
+    pub fn days(&self) -> &[Day] {
+        &self.days
+    }
+
+    /// Index of the current day, one past the last day once the campaign is over.
+    pub fn today_index(&self) -> usize {
+        self.today
+    }
+
+    pub fn commendations(&self) -> u32 {
+        self.commendations
+    }
+
+    pub fn dismissed(&self) -> bool {
+        self.dismissed
+    }
+
+    pub fn today(&self) -> Option<&Day> {
+        self.days.get(self.today)
+    }
+
+    /// The dossier the analyst is currently working on.
+    pub fn current_dossier(&self) -> Option<&Handle<DossierAsset>> {
+        self.today()?.dossiers.front()
+    }
+
+    pub fn stamped(&self) -> Option<Recommendation> {
+        self.stamped
+    }
+
+    /// Keeps track of what's stamped on the current dossier, so it's still there when the
+    /// campaign is picked back up
+    pub fn stamp_current(&mut self, recommendation: Option<Recommendation>) {
+        self.stamped = recommendation;
+    }
+
+    /// The most recently filed decision of the day.
+    pub fn last_decision(&self) -> Option<&Decision> {
+        self.today()?.decisions.last()
+    }
+
+    /// Files the current dossier with the analyst's decision, returning the next one if the day
+    /// isn't over.
+    pub fn finish_dossier(
+        &mut self,
+        recommendation: Recommendation,
+        complies: bool,
+    ) -> Option<&Handle<DossierAsset>> {
+        if complies {
+            self.commendations += 1;
+        } else {
+            self.dismissed = true;
+        }
+        self.stamped = None;
+        let today = self.days.get_mut(self.today)?;
+        if let Some(dossier) = today.dossiers.pop_front() {
+            today.decisions.push(Decision {
+                dossier,
+                stamp: recommendation.stamp,
+                position: recommendation.position,
+            });
+        }
+        today.dossiers.front()
+    }
+
+    /// Moves on to the next day, returning false once the campaign is exhausted. The day
+    /// never goes past the end of the campaign, however often this is called.
+    pub fn advance_day(&mut self) -> bool {
+        self.today = (self.today + 1).min(self.days.len());
+        self.today < self.days.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::asset::HandleId;
+
+    use super::*;
+
+    fn dossier() -> Handle<DossierAsset> {
+        Handle::weak(HandleId::random::<DossierAsset>())
+    }
+
+    /// Two dossiers on the first day, one on the second
+    fn asset() -> CampaignAsset {
+        CampaignAsset {
+            handbook: Handbook { chapters: vec![] },
+            days: vec![vec![dossier(), dossier()], vec![dossier()]],
+        }
+    }
+
+    fn decision(dossier: &Handle<DossierAsset>) -> Decision {
+        Decision {
+            dossier: dossier.clone(),
+            stamp: StampKind::Respect,
+            position: Vec2::ZERO,
+        }
+    }
+
+    #[test]
+    fn advance_day_stops_at_the_end_of_the_campaign() {
+        let mut campaign = Campaign::new(&asset());
+        assert!(campaign.advance_day());
+        assert_eq!(campaign.today_index(), 1);
+        assert!(!campaign.advance_day());
+        assert!(!campaign.advance_day());
+        assert_eq!(campaign.today_index(), 2);
+        assert!(campaign.current_dossier().is_none());
+    }
+
+    #[test]
+    fn restore_picks_up_after_the_filed_dossiers() {
+        let asset = asset();
+        let stamped = Recommendation {
+            stamp: StampKind::Coup,
+            position: Vec2::new(10.0, -20.0),
+            rotation: 0.1,
+        };
+        let campaign = Campaign::restore(
+            &asset,
+            0,
+            1,
+            vec![vec![decision(&asset.days[0][0])]],
+            Some(stamped),
+        )
+        .unwrap();
+        assert_eq!(campaign.current_dossier(), Some(&asset.days[0][1]));
+        assert_eq!(campaign.last_decision().unwrap().dossier, asset.days[0][0]);
+        assert_eq!(campaign.commendations(), 1);
+        assert_eq!(campaign.stamped(), Some(stamped));
+    }
+
+    #[test]
+    fn restore_rejects_a_save_that_does_not_fit() {
+        let asset = asset();
+        // Filed out of order
+        let decisions = vec![vec![decision(&asset.days[0][1])]];
+        assert!(Campaign::restore(&asset, 0, 0, decisions, None).is_none());
+        // More dossiers filed than the day has
+        let decisions = vec![
+            vec![],
+            vec![decision(&asset.days[1][0]), decision(&asset.days[1][0])],
+        ];
+        assert!(Campaign::restore(&asset, 1, 0, decisions, None).is_none());
+        // Past the last day
+        assert!(Campaign::restore(&asset, 2, 0, vec![], None).is_none());
+    }
+}
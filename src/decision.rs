@@ -0,0 +1,175 @@
+//! The analyst's decision on a dossier, from stamping it to filing it.
+//!
+//! A stamped dossier is submitted by dropping it in the outbox or pressing "File Report". It
+//! only gets filed once the undo window runs out, so it can still be taken back out or
+//! restamped until then.
+use bevy::prelude::*;
+
+use crate::{
+    menu::{BUTTON_COLOR, BUTTON_HOVERED_COLOR},
+    Dossier, OnDesk, StampKind, OUTBOX_POSITION,
+};
+
+/// How long a submitted dossier can still be taken back
+const UNDO_SECONDS: f32 = 3.0;
+
+/// The stamp on a dossier that counts, and where it is on the paper
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Recommendation {
+    pub stamp: StampKind,
+    pub position: Vec2,
+    /// How far the mark is tilted, in radians
+    pub rotation: f32,
+}
+
+#[derive(Debug)]
+pub enum DecisionState {
+    Undecided,
+    Stamped(Recommendation),
+    /// Waiting in the outbox for the undo window to run out
+    Submitted(Recommendation, Timer),
+}
+
+impl DecisionState {
+    pub fn recommendation(&self) -> Option<Recommendation> {
+        match *self {
+            DecisionState::Undecided => None,
+            DecisionState::Stamped(recommendation)
+            | DecisionState::Submitted(recommendation, _) => Some(recommendation),
+        }
+    }
+
+    /// Stamping again replaces the decision, taking the dossier back if it was submitted
+    pub fn restamp(&mut self, recommendation: Recommendation) {
+        *self = DecisionState::Stamped(recommendation);
+    }
+
+    /// A stamp outside the recommendation box spoils whatever was decided before it, taking
+    /// the dossier back if it was submitted
+    pub fn spoil(&mut self) {
+        *self = DecisionState::Undecided;
+    }
+
+    pub fn submit(&mut self) {
+        if let DecisionState::Stamped(recommendation) = *self {
+            *self = DecisionState::Submitted(
+                recommendation,
+                Timer::from_seconds(UNDO_SECONDS, TimerMode::Once),
+            );
+        }
+    }
+
+    pub fn cancel(&mut self) {
+        if let DecisionState::Submitted(recommendation, _) = *self {
+            *self = DecisionState::Stamped(recommendation);
+        }
+    }
+}
+
+/// Sent once a submitted dossier's undo window runs out
+pub struct DossierFiled(pub Entity);
+
+pub fn confirm_submissions(
+    time: Res<Time>,
+    mut dossiers: Query<(Entity, &mut Dossier)>,
+    mut filed: EventWriter<DossierFiled>,
+) {
+    for (entity, mut dossier) in &mut dossiers {
+        if let DecisionState::Submitted(_, timer) = &mut dossier.decision {
+            if timer.tick(time.delta()).just_finished() {
+                filed.send(DossierFiled(entity));
+            }
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct FileReportButton;
+
+pub fn spawn_file_report_button(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    size: Size::new(Val::Px(220.0), Val::Px(50.0)),
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        left: Val::Percent(50.0),
+                        bottom: Val::Px(20.0),
+                        ..default()
+                    },
+                    margin: UiRect::left(Val::Px(-110.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: BUTTON_COLOR.into(),
+                visibility: Visibility { is_visible: false },
+                ..default()
+            },
+            FileReportButton,
+            OnDesk,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "File Report",
+                TextStyle {
+                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                    font_size: 28.0,
+                    color: Color::WHITE,
+                },
+            ));
+        });
+}
+
+/// Shows the button once the dossier is stamped, counting down the undo window after submitting
+pub fn update_file_report_button(
+    dossier: Query<&Dossier>,
+    mut button: Query<(&mut Visibility, &Children), With<FileReportButton>>,
+    mut text: Query<&mut Text>,
+) {
+    let (Ok(dossier), Ok((mut visibility, children))) =
+        (dossier.get_single(), button.get_single_mut())
+    else {
+        return;
+    };
+
+    visibility.is_visible = dossier.decision.recommendation().is_some();
+    let label = match &dossier.decision {
+        DecisionState::Submitted(_, timer) => {
+            format!("Undo ({:.0})", timer.remaining_secs().ceil())
+        }
+        _ => "File Report".to_owned(),
+    };
+    let mut text = text.get_mut(children[0]).unwrap();
+    if text.sections[0].value != label {
+        text.sections[0].value = label;
+    }
+}
+
+pub fn press_file_report(
+    mut buttons: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<FileReportButton>),
+    >,
+    mut dossier: Query<(&mut Dossier, &mut Transform)>,
+) {
+    for (interaction, mut color) in &mut buttons {
+        match interaction {
+            Interaction::Clicked => {
+                let (mut dossier, mut transform) = dossier.single_mut();
+                match dossier.decision {
+                    DecisionState::Stamped(_) => {
+                        // Put the dossier in the outbox for the analyst
+                        transform.translation = OUTBOX_POSITION.extend(transform.translation.z);
+                        dossier.decision.submit();
+                    }
+                    DecisionState::Submitted(..) => dossier.decision.cancel(),
+                    DecisionState::Undecided => {}
+                }
+            }
+            Interaction::Hovered => *color = BUTTON_HOVERED_COLOR.into(),
+            Interaction::None => *color = BUTTON_COLOR.into(),
+        }
+    }
+}
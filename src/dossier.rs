@@ -0,0 +1,157 @@
+//! Foreign leader reports, written as RON files under `assets/dossiers/`.
+//!
+//! A dossier file describes everything printed on the paper (facts, policies, headshot)
+//! plus what the handbook's rules look at (the kind of each policy, how the constituency
+//! feels), so new leaders can be added without touching the code.
+//!
+//! It also holds the front page the newspaper runs for each stamp the analyst might choose.
+use bevy::{
+    asset::{AssetLoader, AssetPath, LoadContext, LoadedAsset},
+    reflect::TypeUuid,
+    utils::{BoxedFuture, HashMap},
+};
+use serde::Deserialize;
+
+use crate::StampKind;
+
+#[derive(Debug, Deserialize, TypeUuid)]
+#[uuid = "8ebc4d5e-a53b-46a9-99e6-a3eac331af70"]
+pub struct DossierAsset {
+    pub name: String,
+    pub title: String,
+    pub date_of_birth: String,
+    pub gender: String,
+    pub nationality: String,
+    pub constituency: Constituency,
+    pub eye_color: String,
+    pub hair_color: String,
+    pub policies: Vec<Policy>,
+    /// Path of the headshot, relative to `assets/`.
+    pub photo: String,
+    /// The newspaper's front page for every stamp.
+    pub outcomes: HashMap<StampKind, Outcome>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Policy {
+    pub text: String,
+    /// What the handbook makes of it, if anything.
+    #[serde(default)]
+    pub kind: Option<PolicyKind>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum PolicyKind {
+    /// Health care, education, land for farmers and the like
+    Welfare,
+    /// Nationalizing industry or keeping foreign corporations out
+    AntiCorporate,
+    OilInterference,
+}
+
+impl PolicyKind {
+    /// As the handbook puts it
+    pub fn description(self) -> &'static str {
+        match self {
+            PolicyKind::Welfare => "providing high quality of life to citizens",
+            PolicyKind::AntiCorporate => "interfering with U.S. corporate involvement",
+            PolicyKind::OilInterference => "interfering with oil import",
+        }
+    }
+}
+
+/// How the leader's people feel about them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum Constituency {
+    Loyal,
+    Divided,
+    DemandsReplacement,
+}
+
+impl Constituency {
+    fn label(self) -> &'static str {
+        match self {
+            Constituency::Loyal => "Loyal",
+            Constituency::Divided => "Divided",
+            Constituency::DemandsReplacement => "Demands replacement",
+        }
+    }
+
+    /// As the handbook puts it
+    pub fn description(self) -> &'static str {
+        match self {
+            Constituency::Loyal => "constituents are loyal",
+            Constituency::Divided => "constituents are divided",
+            Constituency::DemandsReplacement => "constituents demand replacement",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Outcome {
+    pub headline: String,
+    pub subheadline: String,
+    /// Path of the front page photo, relative to `assets/`.
+    pub photo: Option<String>,
+    pub body: String,
+}
+
+impl DossierAsset {
+    pub fn facts_text(&self) -> String {
+        format!(
+            "\
+Name: {}
+Title: {}
+D.O.B.: {}
+Gender: {}
+Nationality: {}
+Constituency: {}
+Eye Color: {}
+Hair Color: {}",
+            self.name,
+            self.title,
+            self.date_of_birth,
+            self.gender,
+            self.nationality,
+            self.constituency.label(),
+            self.eye_color,
+            self.hair_color,
+        )
+    }
+}
+
+#[derive(Default)]
+pub struct DossierLoader;
+
+impl AssetLoader for DossierLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let dossier: DossierAsset = ron::de::from_bytes(bytes)?;
+            if let Some(kind) = StampKind::ALL
+                .into_iter()
+                .find(|kind| !dossier.outcomes.contains_key(kind))
+            {
+                return Err(bevy::asset::Error::msg(format!(
+                    "{} has no outcome for {kind:?}",
+                    dossier.name
+                )));
+            }
+
+            // Load the photos alongside so they're ready when the papers are spawned
+            let photos = std::iter::once(&dossier.photo)
+                .chain(dossier.outcomes.values().filter_map(|o| o.photo.as_ref()))
+                .map(|photo| AssetPath::from(photo.as_str()).to_owned())
+                .collect();
+            load_context.set_default_asset(LoadedAsset::new(dossier).with_dependencies(photos));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["dossier.ron"]
+    }
+}
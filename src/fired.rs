@@ -0,0 +1,169 @@
+//! The letter of termination an analyst gets for filing a recommendation against the handbook.
+use bevy::{prelude::*, text::Text2dBounds};
+
+use crate::{
+    campaign::Campaign,
+    dossier::DossierAsset,
+    handbook::Handbook,
+    menu::{MenuButton, BUTTON_COLOR},
+    DragHitBox, DragKind,
+};
+
+#[derive(Component)]
+pub struct OnLetter;
+
+pub fn spawn_letter(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    campaign: Res<Campaign>,
+    handbook: Res<Handbook>,
+    dossiers: Res<Assets<DossierAsset>>,
+) {
+    let decision = campaign
+        .last_decision()
+        .expect("analyst is fired for a filed decision");
+    let dossier = dossiers.get(&decision.dossier).unwrap();
+    let verdict = handbook.verdict(dossier, campaign.today_index());
+    let violated = match verdict.rule {
+        Some((chapter, number)) => format!("Chapter {}, rule {number}", chapter.number),
+        None => "which calls for no intervention here".to_owned(),
+    };
+
+    let paper_size = Vec2::new(420.0, 420.0 * 11.0 / 8.5);
+    let paper_position = Vec2::new(0.0, 0.0);
+    let logo_size = Vec2::new(80.0, 80.0);
+    let margin = 16.0;
+    let mono_font = asset_server.load("fonts/FiraMono-Medium.ttf");
+    // Children are placed relative to the center of the paper
+    let paper_top_left = Vec2::new(-paper_size.x / 2.0, paper_size.y / 2.0);
+    let body_offset = Vec2::new(margin, -logo_size.y - margin * 3.0);
+
+    commands
+        .spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: Color::WHITE,
+                    custom_size: Some(paper_size),
+                    ..default()
+                },
+                transform: Transform::from_translation(paper_position.extend(80.0)),
+                ..default()
+            },
+            DragHitBox {
+                size: paper_size,
+                kind: DragKind::Document,
+                alpha_mask: false,
+            },
+            OnLetter,
+        ))
+        .with_children(|paper| {
+            // The seal from the top left of the case file template
+            paper.spawn(SpriteBundle {
+                texture: asset_server.load("CIA_template.jpg"),
+                sprite: Sprite {
+                    custom_size: Some(logo_size),
+                    rect: Some(Rect::new(18.0, 14.0, 138.0, 134.0)),
+                    ..default()
+                },
+                transform: Transform::from_translation(
+                    (paper_top_left
+                        + Vec2::new(logo_size.x / 2.0, -logo_size.y / 2.0)
+                        + Vec2::new(margin, -margin))
+                    .extend(1.0),
+                ),
+                ..default()
+            });
+
+            paper.spawn(Text2dBundle {
+                text: Text::from_section(
+                    "Central Intelligence Agency\nOffice of the Director".to_owned(),
+                    TextStyle {
+                        font: mono_font.clone(),
+                        font_size: 18.0,
+                        color: Color::BLACK,
+                    },
+                ),
+                transform: Transform::from_translation(
+                    (paper_top_left + Vec2::new(logo_size.x + margin * 2.0, -margin * 2.0))
+                        .extend(1.0),
+                ),
+                ..default()
+            });
+
+            paper.spawn(Text2dBundle {
+                text: Text::from_section(
+                    format!(
+                        "\
+NOTICE OF TERMINATION
+
+Analyst,
+
+Your recommendation to {} {}, {}, is in direct violation of the Analyst Handbook, {}:
+
+\"{}\"
+
+Your employment with the Central Intelligence Agency is terminated, effective immediately. Return your badge and stamps to the front desk on your way out.
+
+Director of Central Intelligence",
+                        decision.stamp.verb(),
+                        dossier.name,
+                        dossier.title,
+                        violated,
+                        verdict.rule_text(),
+                    ),
+                    TextStyle {
+                        font: mono_font,
+                        font_size: 14.0,
+                        color: Color::BLACK,
+                    },
+                ),
+                text_2d_bounds: Text2dBounds {
+                    size: Vec2::new(
+                        paper_size.x - margin * 2.0,
+                        paper_size.y + body_offset.y - margin,
+                    ),
+                },
+                transform: Transform::from_translation((paper_top_left + body_offset).extend(1.0)),
+                ..default()
+            });
+        });
+
+    // Nothing left to do at the agency but look back on the career it ended
+    commands
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    size: Size::new(Val::Px(280.0), Val::Px(50.0)),
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        right: Val::Px(20.0),
+                        bottom: Val::Px(20.0),
+                        ..default()
+                    },
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: BUTTON_COLOR.into(),
+                ..default()
+            },
+            MenuButton::GoToReview,
+            OnLetter,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Performance Review",
+                TextStyle {
+                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                    font_size: 28.0,
+                    color: Color::WHITE,
+                },
+            ));
+        });
+}
+
+pub fn despawn_letter(query: Query<Entity, With<OnLetter>>, mut commands: Commands) {
+    for entity in &query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,548 @@
+//! The analyst handbook: which stamp a dossier calls for, written as conditions on what the
+//! dossier says so the same rules are printed in the handbook and used to judge the analyst.
+//!
+//! The handbook is part of `assets/main.campaign.ron`, split into chapters. Its rules are
+//! checked in order, skipping any that haven't come into effect yet: the first one whose
+//! condition holds for a dossier decides it. A dossier none of them decide calls for respect,
+//! see [`OTHERWISE`].
+//!
+//! On the desk it's a document opened at one page at a time, starting at the table of
+//! contents. Clicking the dog-eared corners or an entry in the contents turns the page.
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::{
+    campaign::Campaign,
+    contains,
+    dossier::{Constituency, DossierAsset, PolicyKind},
+    layout::DocumentLayout,
+    DragHitBox, DragKind, OnDesk, StampKind, UnderMouse,
+};
+
+/// What the handbook calls for when none of its rules apply, printed as "Otherwise, respect"
+const OTHERWISE: StampKind = StampKind::Respect;
+
+#[derive(Debug, Clone, Deserialize, Resource)]
+pub struct Handbook {
+    pub chapters: Vec<Chapter>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Chapter {
+    /// Like the 10 of "Chapter 10"
+    pub number: u32,
+    pub title: String,
+    /// Printed above the rules
+    #[serde(default)]
+    pub text: String,
+    #[serde(default)]
+    pub rules: Vec<Rule>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Rule {
+    pub stamp: StampKind,
+    pub when: Condition,
+    /// Day of the campaign the rule comes into effect, counting from 1
+    #[serde(default = "first_day")]
+    pub from_day: usize,
+    /// Printed instead of the wording made up from the condition
+    #[serde(default)]
+    pub text: Option<String>,
+}
+
+fn first_day() -> usize {
+    1
+}
+
+/// Something a dossier can say about a leader
+#[derive(Debug, Clone, Deserialize)]
+pub enum Condition {
+    /// One of their policies is of this kind
+    Policy(PolicyKind),
+    Constituency(Constituency),
+    Any(Vec<Condition>),
+    All(Vec<Condition>),
+    Not(Box<Condition>),
+}
+
+/// The stamp the handbook calls for on a dossier, and the rule that calls for it
+pub struct Verdict<'a> {
+    /// The chapter and number of the rule, or `None` if no rule applies
+    pub rule: Option<(&'a Chapter, usize)>,
+    pub stamp: StampKind,
+}
+
+impl Verdict<'_> {
+    /// The text of the rule that decides it, or of what to do if none apply
+    pub fn rule_text(&self) -> String {
+        match self.rule {
+            Some((chapter, number)) => chapter.rules[number - 1].text(),
+            None => otherwise_text(),
+        }
+    }
+}
+
+impl Handbook {
+    /// `today` is the index of the campaign's current day
+    pub fn verdict(&self, dossier: &DossierAsset, today: usize) -> Verdict<'_> {
+        self.chapters
+            .iter()
+            .flat_map(|chapter| {
+                chapter
+                    .rules
+                    .iter()
+                    .enumerate()
+                    .map(move |(i, rule)| (chapter, i + 1, rule))
+            })
+            .find(|(_, _, rule)| rule.in_effect(today) && rule.when.holds(dossier))
+            .map_or(
+                Verdict {
+                    rule: None,
+                    stamp: OTHERWISE,
+                },
+                |(chapter, number, rule)| Verdict {
+                    rule: Some((chapter, number)),
+                    stamp: rule.stamp,
+                },
+            )
+    }
+
+    /// Whether the handbook allows stamping `dossier` with `stamp`
+    pub fn complies(&self, dossier: &DossierAsset, stamp: StampKind, today: usize) -> bool {
+        self.verdict(dossier, today).stamp == stamp
+    }
+
+    /// The chapters that are printed on `today`: those with no rules, or some in effect
+    fn chapters_on(&self, today: usize) -> Vec<&Chapter> {
+        self.chapters
+            .iter()
+            .filter(|chapter| {
+                chapter.rules.is_empty() || chapter.rules.iter().any(|rule| rule.in_effect(today))
+            })
+            .collect()
+    }
+}
+
+fn otherwise_text() -> String {
+    format!("Otherwise, {}", OTHERWISE.verb())
+}
+
+impl Rule {
+    fn in_effect(&self, today: usize) -> bool {
+        today + 1 >= self.from_day
+    }
+
+    /// Like "Assassinate if providing high quality of life to citizens", unless the campaign
+    /// words it itself
+    pub fn text(&self) -> String {
+        if let Some(text) = &self.text {
+            return text.clone();
+        }
+        let verb = self.stamp.verb();
+        let mut text = verb[..1].to_uppercase();
+        text.push_str(&verb[1..]);
+        format!("{text} if {}", self.when.describe())
+    }
+}
+
+impl Chapter {
+    /// The rules in effect, each with its number
+    fn rule_lines(&self, today: usize) -> Vec<(String, String)> {
+        self.rules
+            .iter()
+            .enumerate()
+            .filter(|(_, rule)| rule.in_effect(today))
+            .map(|(i, rule)| {
+                let new = if rule.from_day > 1 && rule.from_day == today + 1 {
+                    " (new)"
+                } else {
+                    ""
+                };
+                (format!("{}.", i + 1), format!("{}{new}", rule.text()))
+            })
+            .collect()
+    }
+}
+
+impl Condition {
+    pub fn holds(&self, dossier: &DossierAsset) -> bool {
+        match self {
+            Condition::Policy(kind) => dossier
+                .policies
+                .iter()
+                .any(|policy| policy.kind == Some(*kind)),
+            Condition::Constituency(constituency) => dossier.constituency == *constituency,
+            Condition::Any(conditions) => conditions.iter().any(|c| c.holds(dossier)),
+            Condition::All(conditions) => conditions.iter().all(|c| c.holds(dossier)),
+            Condition::Not(condition) => !condition.holds(dossier),
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Condition::Policy(kind) => kind.description().to_owned(),
+            Condition::Constituency(constituency) => constituency.description().to_owned(),
+            Condition::Any(conditions) => describe_all(conditions, " or "),
+            Condition::All(conditions) => describe_all(conditions, " and "),
+            Condition::Not(condition) => format!("not {}", condition.describe()),
+        }
+    }
+}
+
+fn describe_all(conditions: &[Condition], separator: &str) -> String {
+    conditions
+        .iter()
+        .map(Condition::describe)
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+const PAPER_SIZE: Vec2 = Vec2::new(300.0, 300.0 * 11.0 / 8.5);
+const LOGO_SIZE: Vec2 = Vec2::new(40.0, 40.0);
+const CORNER_SIZE: Vec2 = Vec2::new(28.0, 28.0);
+const MARGIN: f32 = 12.0;
+
+/// The handbook on the desk, open at a page: the table of contents, then one per chapter
+#[derive(Component)]
+pub struct OpenHandbook {
+    page: usize,
+}
+
+/// Everything printed on the open page, replaced when it's turned
+#[derive(Component)]
+pub struct PageContent;
+
+/// Somewhere on the handbook to click to turn the page
+#[derive(Component)]
+pub struct PageTurn {
+    to: TurnTo,
+    size: Vec2,
+}
+
+#[derive(Clone, Copy)]
+enum TurnTo {
+    Previous,
+    Next,
+    Page(usize),
+}
+
+pub fn spawn_handbook(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands
+        .spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: Color::WHITE,
+                    custom_size: Some(PAPER_SIZE),
+                    ..default()
+                },
+                // A little askew, like it was dropped there
+                transform: Transform::from_xyz(250.0, 100.0, 20.0)
+                    .with_rotation(Quat::from_rotation_z(-0.03)),
+                ..default()
+            },
+            DragHitBox {
+                size: PAPER_SIZE,
+                kind: DragKind::Document,
+                alpha_mask: false,
+            },
+            OpenHandbook { page: 0 },
+            OnDesk,
+        ))
+        // Children are placed relative to the center of the paper
+        .with_children(|paper| {
+            paper.spawn(SpriteBundle {
+                texture: asset_server.load("cia.png"),
+                sprite: Sprite {
+                    custom_size: Some(LOGO_SIZE),
+                    ..default()
+                },
+                transform: Transform::from_xyz(0.0, PAPER_SIZE.y / 2.0 - LOGO_SIZE.y / 2.0, 1.0),
+                ..default()
+            });
+
+            let mono_font = asset_server.load("fonts/FiraMono-Medium.ttf");
+            for (to, x, arrow) in [(TurnTo::Previous, -1.0, "<"), (TurnTo::Next, 1.0, ">")] {
+                paper
+                    .spawn((
+                        SpriteBundle {
+                            sprite: Sprite {
+                                color: Color::rgb(0.85, 0.85, 0.82),
+                                custom_size: Some(CORNER_SIZE),
+                                ..default()
+                            },
+                            transform: Transform::from_xyz(
+                                x * (PAPER_SIZE.x - CORNER_SIZE.x) / 2.0,
+                                (CORNER_SIZE.y - PAPER_SIZE.y) / 2.0,
+                                2.0,
+                            ),
+                            ..default()
+                        },
+                        PageTurn {
+                            to,
+                            size: CORNER_SIZE,
+                        },
+                    ))
+                    .with_children(|corner| {
+                        corner.spawn(Text2dBundle {
+                            text: Text::from_section(
+                                arrow,
+                                TextStyle {
+                                    font: mono_font.clone(),
+                                    font_size: 18.0,
+                                    color: Color::DARK_GRAY,
+                                },
+                            )
+                            .with_alignment(TextAlignment::CENTER),
+                            transform: Transform::from_xyz(0.0, 0.0, 1.0),
+                            ..default()
+                        });
+                    });
+            }
+        });
+}
+
+/// Turns the page when one of the handbook's corners or a contents entry is clicked. The click
+/// is used up turning the page, so it doesn't pick the handbook up too
+pub fn turn_handbook_pages(
+    mut input: ResMut<Input<MouseButton>>,
+    under_mouse: UnderMouse,
+    handbook: Res<Handbook>,
+    campaign: Res<Campaign>,
+    mut open: Query<(&mut OpenHandbook, &Children)>,
+    pages_content: Query<&Children, With<PageContent>>,
+    turns: Query<(&PageTurn, &GlobalTransform, &Visibility)>,
+) {
+    if !input.just_pressed(MouseButton::Left) {
+        return;
+    }
+    let Some(Ok((mut open, children))) = under_mouse.front().map(|front| open.get_mut(front))
+    else {
+        return;
+    };
+    // The corners are on the paper, the contents entries on the page printed on it
+    let parts = children
+        .iter()
+        .chain(pages_content.iter_many(children).flatten());
+    let clicked = turns
+        .iter_many(parts)
+        .find(|(turn, transform, visibility)| {
+            visibility.is_visible && contains(transform, turn.size, under_mouse.mouse.position)
+        });
+    if let Some((turn, ..)) = clicked {
+        let pages = handbook.chapters_on(campaign.today_index()).len() + 1;
+        let page = match turn.to {
+            TurnTo::Previous => open.page.saturating_sub(1),
+            TurnTo::Next => (open.page + 1).min(pages - 1),
+            TurnTo::Page(page) => page,
+        };
+        if open.page != page {
+            open.page = page;
+        }
+        input.clear_just_pressed(MouseButton::Left);
+    }
+}
+
+/// Prints the open page, hiding the corners that would turn past either end
+pub fn show_handbook_page(
+    asset_server: Res<AssetServer>,
+    handbook: Res<Handbook>,
+    campaign: Res<Campaign>,
+    open: Query<(Entity, &OpenHandbook, &Children), Changed<OpenHandbook>>,
+    content: Query<(), With<PageContent>>,
+    mut turns: Query<(&PageTurn, &mut Visibility)>,
+    mut commands: Commands,
+) {
+    let today = campaign.today_index();
+    let chapters = handbook.chapters_on(today);
+    let last_with_rules = chapters
+        .iter()
+        .rposition(|chapter| chapter.rules.iter().any(|rule| rule.in_effect(today)));
+
+    for (entity, open, children) in &open {
+        for &child in children {
+            if content.contains(child) {
+                commands.entity(child).despawn_recursive();
+            }
+            if let Ok((turn, mut visibility)) = turns.get_mut(child) {
+                visibility.is_visible = match turn.to {
+                    TurnTo::Previous => open.page > 0,
+                    TurnTo::Next => open.page < chapters.len(),
+                    TurnTo::Page(_) => true,
+                };
+            }
+        }
+
+        let header = match open.page {
+            0 => "Contents".to_owned(),
+            page => {
+                let chapter = chapters[page - 1];
+                format!("Chapter {}: {}", chapter.number, chapter.title)
+            }
+        };
+        let mono_font = asset_server.load("fonts/FiraMono-Medium.ttf");
+        commands.entity(entity).with_children(|paper| {
+            paper
+                .spawn((SpatialBundle::default(), PageContent))
+                .with_children(|page| {
+                    let mut layout = DocumentLayout::new(page, PAPER_SIZE, MARGIN, mono_font);
+                    // Under the logo, which stays put from page to page
+                    layout.skip(LOGO_SIZE.y);
+                    layout.centered(&format!("Analyst Handbook\n{header}"), 18.0);
+
+                    match open.page {
+                        0 => {
+                            for (i, chapter) in chapters.iter().enumerate() {
+                                let size = layout.row_size(14.0);
+                                layout
+                                    .row(
+                                        &format!("{}. {}", chapter.number, chapter.title),
+                                        &format!("{}", i + 2),
+                                        14.0,
+                                    )
+                                    .insert(PageTurn {
+                                        to: TurnTo::Page(i + 1),
+                                        size,
+                                    });
+                            }
+                        }
+                        page => {
+                            let chapter = chapters[page - 1];
+                            if !chapter.text.is_empty() {
+                                layout.paragraph(&chapter.text, 14.0);
+                            }
+                            let rules = chapter.rule_lines(today);
+                            layout.list(
+                                rules
+                                    .iter()
+                                    .map(|(number, text)| (number.clone(), text.as_str())),
+                                14.0,
+                            );
+                            if last_with_rules == Some(page - 1) {
+                                layout.paragraph(&otherwise_text(), 14.0);
+                            }
+                        }
+                    }
+
+                    layout.footer(&format!("{}", open.page + 1), 12.0);
+                });
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dossier::{Constituency, Policy, PolicyKind};
+
+    fn rule(stamp: StampKind, when: Condition, from_day: usize) -> Rule {
+        Rule {
+            stamp,
+            when,
+            from_day,
+            text: None,
+        }
+    }
+
+    fn handbook() -> Handbook {
+        Handbook {
+            chapters: vec![
+                Chapter {
+                    number: 1,
+                    title: "Conduct".to_string(),
+                    text: String::new(),
+                    rules: vec![],
+                },
+                Chapter {
+                    number: 10,
+                    title: "Intervention Policy".to_string(),
+                    text: String::new(),
+                    rules: vec![
+                        rule(
+                            StampKind::Assassinate,
+                            Condition::Policy(PolicyKind::Welfare),
+                            1,
+                        ),
+                        rule(
+                            StampKind::Coup,
+                            Condition::Constituency(Constituency::DemandsReplacement),
+                            1,
+                        ),
+                        rule(
+                            StampKind::Coup,
+                            Condition::All(vec![
+                                Condition::Constituency(Constituency::Divided),
+                                Condition::Not(Box::new(Condition::Policy(PolicyKind::Welfare))),
+                            ]),
+                            2,
+                        ),
+                    ],
+                },
+            ],
+        }
+    }
+
+    fn dossier(constituency: Constituency, policies: &[PolicyKind]) -> DossierAsset {
+        DossierAsset {
+            name: "Leader".to_string(),
+            title: "President".to_string(),
+            date_of_birth: String::new(),
+            gender: String::new(),
+            nationality: String::new(),
+            constituency,
+            eye_color: String::new(),
+            hair_color: String::new(),
+            policies: policies
+                .iter()
+                .map(|&kind| Policy {
+                    text: kind.description().to_string(),
+                    kind: Some(kind),
+                })
+                .collect(),
+            photo: String::new(),
+            outcomes: default(),
+        }
+    }
+
+    /// The stamp called for and the number of the rule calling for it
+    fn judge(
+        handbook: &Handbook,
+        dossier: &DossierAsset,
+        today: usize,
+    ) -> (StampKind, Option<usize>) {
+        let verdict = handbook.verdict(dossier, today);
+        (verdict.stamp, verdict.rule.map(|(_, number)| number))
+    }
+
+    #[test]
+    fn verdict_follows_the_first_rule_that_holds() {
+        let handbook = handbook();
+        let welfare = dossier(Constituency::DemandsReplacement, &[PolicyKind::Welfare]);
+        assert_eq!(
+            judge(&handbook, &welfare, 0),
+            (StampKind::Assassinate, Some(1))
+        );
+        let replace = dossier(
+            Constituency::DemandsReplacement,
+            &[PolicyKind::AntiCorporate],
+        );
+        assert_eq!(judge(&handbook, &replace, 0), (StampKind::Coup, Some(2)));
+    }
+
+    #[test]
+    fn verdict_skips_rules_not_yet_in_effect() {
+        let handbook = handbook();
+        let divided = dossier(Constituency::Divided, &[]);
+        assert_eq!(judge(&handbook, &divided, 0), (OTHERWISE, None));
+        assert_eq!(judge(&handbook, &divided, 1), (StampKind::Coup, Some(3)));
+    }
+
+    #[test]
+    fn verdict_falls_back_to_otherwise() {
+        let handbook = handbook();
+        let loyal = dossier(Constituency::Loyal, &[PolicyKind::OilInterference]);
+        let verdict = handbook.verdict(&loyal, 1);
+        assert_eq!(verdict.stamp, StampKind::Respect);
+        assert_eq!(verdict.rule_text(), "Otherwise, respect");
+    }
+}
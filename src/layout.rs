@@ -0,0 +1,351 @@
+//! Laying out paper documents from the top down, each block placed under the last, so text
+//! wraps to the paper and nothing overlaps whatever it says.
+//!
+//! Text is measured as FiraMono, whose glyphs are all the same width, so how far a block
+//! reaches down the paper is known without waiting for the font to load. Blocks at the bottom
+//! of the paper, like the stamp box, are placed from the bottom up instead, and the body is
+//! scaled down to fit above them if it's too long.
+use bevy::{
+    ecs::system::{EntityCommands, Insert},
+    prelude::*,
+    text::Text2dBounds,
+};
+
+use crate::stamp_zone::spawn_stamp_zone_outline;
+
+/// Width of every FiraMono glyph, relative to the font size
+const GLYPH_ADVANCE: f32 = 0.6;
+/// Height of a line of FiraMono, relative to the font size
+const LINE_HEIGHT: f32 = 1.2;
+/// Space left between blocks
+const BLOCK_GAP: f32 = 8.0;
+
+/// Places blocks down a paper, as children of it relative to its center
+pub struct DocumentLayout<'p, 'w, 's, 'a> {
+    paper: &'p mut ChildBuilder<'w, 's, 'a>,
+    size: Vec2,
+    margin: f32,
+    font: Handle<Font>,
+    /// How far down from the top of the paper the next block goes
+    cursor: f32,
+    /// How far down from the top of the paper the first block went
+    body_top: f32,
+    /// Everything placed from the top down, to scale down if it doesn't fit
+    body: Vec<(Entity, Transform)>,
+}
+
+impl<'p, 'w, 's, 'a> DocumentLayout<'p, 'w, 's, 'a> {
+    pub fn new(
+        paper: &'p mut ChildBuilder<'w, 's, 'a>,
+        size: Vec2,
+        margin: f32,
+        font: Handle<Font>,
+    ) -> Self {
+        DocumentLayout {
+            paper,
+            size,
+            margin,
+            font,
+            cursor: margin,
+            body_top: margin,
+            body: Vec::new(),
+        }
+    }
+
+    fn left(&self) -> f32 {
+        -self.size.x / 2.0 + self.margin
+    }
+
+    fn top(&self) -> f32 {
+        self.size.y / 2.0 - self.cursor
+    }
+
+    fn width(&self) -> f32 {
+        self.size.x - self.margin * 2.0
+    }
+
+    fn style(&self, font_size: f32) -> TextStyle {
+        TextStyle {
+            font: self.font.clone(),
+            font_size,
+            color: Color::BLACK,
+        }
+    }
+
+    /// Leaves some space, like for something already on the paper
+    pub fn skip(&mut self, height: f32) {
+        self.cursor += height;
+        if self.body.is_empty() {
+            self.body_top = self.cursor;
+        }
+    }
+
+    /// Spawns part of the body at `transform`, over the one in the bundle, keeping track of it
+    /// to scale down later
+    fn spawn_body(
+        &mut self,
+        bundle: impl Bundle,
+        transform: Transform,
+    ) -> EntityCommands<'w, 's, '_> {
+        let mut entity = self.paper.spawn(bundle);
+        entity.insert(transform);
+        self.body.push((entity.id(), transform));
+        entity
+    }
+
+    /// Wrapped text at the cursor, starting at `x` and `width` wide. Comes back with its height
+    fn spawn_text(&mut self, text: &str, font_size: f32, x: f32, width: f32) -> f32 {
+        let height = text_height(text, font_size, width);
+        let y = self.top();
+        let style = self.style(font_size);
+        // Only the width is bounded, so the text is never cut short if it's taller than measured
+        self.spawn_body(
+            Text2dBundle {
+                text: Text::from_section(text, style),
+                text_2d_bounds: Text2dBounds {
+                    size: Vec2::new(width, f32::MAX),
+                },
+                ..default()
+            },
+            Transform::from_xyz(x, y, 1.0),
+        );
+        height
+    }
+
+    /// An image in the top left with text beside it, like a letterhead or a headshot with the
+    /// facts about the person
+    pub fn image_with_text(
+        &mut self,
+        image: Handle<Image>,
+        image_size: Vec2,
+        text: &str,
+        font_size: f32,
+    ) {
+        let top = self.top();
+        let left = self.left();
+        self.spawn_body(
+            SpriteBundle {
+                texture: image,
+                sprite: Sprite {
+                    custom_size: Some(image_size),
+                    ..default()
+                },
+                ..default()
+            },
+            Transform::from_xyz(left + image_size.x / 2.0, top - image_size.y / 2.0, 1.0),
+        );
+        let x = self.left() + image_size.x + self.margin;
+        let width = self.width() - image_size.x - self.margin;
+        let text_height = self.spawn_text(text, font_size, x, width);
+        self.cursor += image_size.y.max(text_height) + BLOCK_GAP;
+    }
+
+    /// Text centered across the paper, like a title
+    pub fn centered(&mut self, text: &str, font_size: f32) {
+        let width = self.width();
+        let height = text_height(text, font_size, width);
+        let top = self.top();
+        let style = self.style(font_size);
+        self.spawn_body(
+            Text2dBundle {
+                text: Text::from_section(text, style).with_alignment(TextAlignment::TOP_CENTER),
+                text_2d_bounds: Text2dBounds {
+                    size: Vec2::new(width, f32::MAX),
+                },
+                ..default()
+            },
+            Transform::from_xyz(0.0, top, 1.0),
+        );
+        self.cursor += height + BLOCK_GAP;
+    }
+
+    /// Text wrapped to the margins
+    pub fn paragraph(&mut self, text: &str, font_size: f32) {
+        let height = self.spawn_text(text, font_size, self.left(), self.width());
+        self.cursor += height + BLOCK_GAP;
+    }
+
+    /// Items marked with a bullet, wrapping under themselves rather than the bullet
+    pub fn bullets<'t>(&mut self, items: impl IntoIterator<Item = &'t str>, font_size: f32) {
+        self.list(
+            items.into_iter().map(|item| ("•".to_owned(), item)),
+            font_size,
+        );
+    }
+
+    /// Items marked with whatever's given for each, like numbers, wrapping under themselves
+    pub fn list<'t>(&mut self, items: impl IntoIterator<Item = (String, &'t str)>, font_size: f32) {
+        let items: Vec<_> = items.into_iter().collect();
+        // Every item lines up after the widest mark
+        let columns = items
+            .iter()
+            .map(|(mark, _)| mark.chars().count())
+            .max()
+            .unwrap_or(0);
+        let indent = (columns + 1) as f32 * font_size * GLYPH_ADVANCE;
+        for (mark, item) in items {
+            let (left, top) = (self.left(), self.top());
+            let style = self.style(font_size);
+            self.spawn_body(
+                Text2dBundle {
+                    text: Text::from_section(mark, style),
+                    ..default()
+                },
+                Transform::from_xyz(left, top, 1.0),
+            );
+            let height =
+                self.spawn_text(item, font_size, self.left() + indent, self.width() - indent);
+            self.cursor += height;
+        }
+        self.cursor += BLOCK_GAP;
+    }
+
+    /// A line with text at either end, like a table of contents entry. Comes back with the
+    /// line, which is centered on it, to add more to
+    pub fn row(&mut self, left: &str, right: &str, font_size: f32) -> EntityCommands<'w, 's, '_> {
+        let height = font_size * LINE_HEIGHT;
+        let (x, width) = (self.left(), self.width());
+        let left_style = self.style(font_size);
+        let right_style = self.style(font_size);
+        let y = self.top() - height / 2.0;
+        self.cursor += height;
+        let mut row = self.spawn_body(
+            SpatialBundle::default(),
+            Transform::from_xyz(x + width / 2.0, y, 1.0),
+        );
+        row.with_children(|row| {
+            row.spawn(Text2dBundle {
+                text: Text::from_section(left, left_style)
+                    .with_alignment(TextAlignment::CENTER_LEFT),
+                transform: Transform::from_xyz(-width / 2.0, 0.0, 0.0),
+                ..default()
+            });
+            row.spawn(Text2dBundle {
+                text: Text::from_section(right, right_style)
+                    .with_alignment(TextAlignment::CENTER_RIGHT),
+                transform: Transform::from_xyz(width / 2.0, 0.0, 0.0),
+                ..default()
+            });
+        });
+        row
+    }
+
+    /// Size of a line of text, for things lined up with a [`row`](Self::row)
+    pub fn row_size(&self, font_size: f32) -> Vec2 {
+        Vec2::new(self.width(), font_size * LINE_HEIGHT)
+    }
+
+    /// A line of text centered at the bottom of the paper, like a page number
+    pub fn footer(&mut self, text: &str, font_size: f32) {
+        self.fit_above(self.size.y - self.margin - font_size * LINE_HEIGHT);
+        let style = self.style(font_size);
+        self.paper.spawn(Text2dBundle {
+            text: Text::from_section(text, style).with_alignment(TextAlignment::BOTTOM_CENTER),
+            transform: Transform::from_xyz(0.0, -self.size.y / 2.0 + self.margin, 1.0),
+            ..default()
+        });
+    }
+
+    /// A box at the bottom of the paper for the recommendation stamp, with a label over it.
+    /// Comes back with where the box is on the paper
+    pub fn stamp_box(&mut self, label: &str, font_size: f32, size: Vec2) -> Rect {
+        let bottom = -self.size.y / 2.0 + self.margin;
+        let zone = Rect::from_center_size(Vec2::new(0.0, bottom + size.y / 2.0), size);
+        let label_height = text_height(label, font_size, self.width());
+        self.fit_above(self.size.y / 2.0 - zone.max.y - label_height);
+        let style = self.style(font_size);
+        self.paper.spawn(Text2dBundle {
+            text: Text::from_section(label, style).with_alignment(TextAlignment::BOTTOM_CENTER),
+            transform: Transform::from_xyz(0.0, zone.max.y, 1.0),
+            ..default()
+        });
+        spawn_stamp_zone_outline(self.paper, zone);
+        zone
+    }
+
+    /// Scales the body down, about the middle of its top, if it reaches further than `limit`
+    /// down from the top of the paper
+    fn fit_above(&mut self, limit: f32) {
+        // The gap after the last block doesn't have to fit
+        let height = self.cursor - BLOCK_GAP - self.body_top;
+        let room = limit - BLOCK_GAP - self.body_top;
+        if height <= room {
+            return;
+        }
+        if room <= 0.0 {
+            warn!("no room on the paper for a document's body");
+            return;
+        }
+        let scale = room / height;
+        let top = self.size.y / 2.0 - self.body_top;
+        for (entity, transform) in &mut self.body {
+            transform.translation.x *= scale;
+            transform.translation.y = top - (top - transform.translation.y) * scale;
+            transform.scale *= Vec2::splat(scale).extend(1.0);
+            self.paper.add_command(Insert {
+                entity: *entity,
+                bundle: *transform,
+            });
+        }
+        self.cursor = self.body_top + (self.cursor - self.body_top) * scale;
+    }
+}
+
+/// How tall `text` is once wrapped to `width`
+fn text_height(text: &str, font_size: f32, width: f32) -> f32 {
+    let columns = ((width / (font_size * GLYPH_ADVANCE)) as usize).max(1);
+    let lines: usize = text.lines().map(|line| wrapped_lines(line, columns)).sum();
+    lines.max(1) as f32 * font_size * LINE_HEIGHT
+}
+
+/// How many lines a line of text wraps to, breaking between words, or inside words too long
+/// to fit on a line of their own
+fn wrapped_lines(line: &str, columns: usize) -> usize {
+    let mut lines = 1;
+    let mut used = 0;
+    for word in line.split(' ') {
+        let len = word.chars().count();
+        if used > 0 && used + 1 + len > columns {
+            lines += 1;
+            used = 0;
+        }
+        if used > 0 {
+            used += 1;
+        }
+        used += len;
+        while used > columns {
+            lines += 1;
+            used -= columns;
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrapped_lines_breaks_between_words() {
+        assert_eq!(wrapped_lines("", 10), 1);
+        assert_eq!(wrapped_lines("abcde fghi", 10), 1);
+        assert_eq!(wrapped_lines("abcde fghij", 10), 2);
+        assert_eq!(wrapped_lines("the quick brown fox", 10), 2);
+    }
+
+    #[test]
+    fn wrapped_lines_breaks_inside_long_words() {
+        assert_eq!(wrapped_lines("abcdefghijklmnopqrstuvwxy", 10), 3);
+        assert_eq!(wrapped_lines("ab abcdefghijklmnopqrst", 10), 3);
+    }
+
+    #[test]
+    fn text_height_counts_wrapped_and_explicit_lines() {
+        let line = 10.0 * LINE_HEIGHT;
+        // Ten columns to a line
+        let width = 65.0;
+        assert_eq!(text_height("", 10.0, width), line);
+        assert_eq!(text_height("abcde fghi", 10.0, width), line);
+        assert_eq!(text_height("abcde fghij\nxyz", 10.0, width), 3.0 * line);
+    }
+}
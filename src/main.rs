@@ -4,26 +4,27 @@
 //! - add BeingDragged to them
 //!
 //! On mouse move,
-//! - translate all BeingDragged entities by mouse delta
-//! - TODO: check boundaries
+//! - translate all BeingDragged entities by mouse delta, their children follow
+//! - keep them within the DeskBounds
 //!
 //! On mouse release,
 //! - remove BeingDragged from all
+//! - send Dropped for the front-most DropTarget under the dropped entity that accepts it
 //!
 //! TODO list
 //! - [x] Create dossier
 //!     - Add text for dossier
 //!     - Add image
-//! - [ ] Create newspaper
+//! - [x] Create newspaper
 //! - [x] Create stamper
-//! - [ ] Create game logic
+//! - [x] Create game logic
 //! - [x] Create checklist for your job
 //!
 //!
 //! Game flow:
 //! 1. Title screen:
-//!         Welcome to the CIA
-//!             Start Game
+//!    Welcome to the CIA
+//!    Start Game
 //! 2. Some text "Welcome to your first day as an analyst at the CIA!"
 //! 3. Drag dossiers from inbox onto desk (don't all have to be people. could be other issues)
 //! 4. Choose stamp to place under "Analyst Recommendation" (for foreign leaders, assassinate or respect)
@@ -37,6 +38,7 @@
 //! 4. Click continue
 //! 5. If you clicked assassinate, show real newspaper of egypt mourning him
 //!    If you clicked respect, show letter of termination explaining why you're fired
+//!
 //! Ideas
 //! - Ga
 //!
@@ -54,59 +56,178 @@
 //!
 //!
 #![allow(clippy::type_complexity)]
-use std::mem;
+mod audio;
+mod bounds;
+mod campaign;
+mod decision;
+mod dossier;
+mod fired;
+mod handbook;
+mod layout;
+mod menu;
+mod newspaper;
+mod review;
+mod save;
+mod settings;
+mod stack;
+mod stamp_press;
+mod stamp_zone;
+mod storage;
+mod transition;
+
+use std::{marker::PhantomData, mem};
 
-use bevy::{prelude::*, text::Text2dBounds};
-use iyes_loopless::{
-    prelude::{AppLooplessStateExt, IntoConditionalSystem},
-    state::NextState,
+use audio::{
+    play_newspaper_sting, play_sounds, rustle_picked_up, start_ambience, stop_ambience,
+    update_ambience_volume, PlaySound, Sound,
+};
+use bevy::{
+    asset::LoadState, ecs::system::SystemParam, prelude::*, render::render_resource::TextureFormat,
+};
+use bounds::{keep_on_desk, update_desk_bounds, DeskBounds};
+use campaign::{Campaign, CampaignAsset, CampaignLoader};
+use decision::{
+    confirm_submissions, press_file_report, spawn_file_report_button, update_file_report_button,
+    DecisionState, DossierFiled, Recommendation,
+};
+use dossier::{DossierAsset, DossierLoader};
+use fired::{despawn_letter, spawn_letter};
+use handbook::{show_handbook_page, spawn_handbook, turn_handbook_pages, Handbook};
+use iyes_loopless::prelude::{AppLooplessStateExt, ConditionHelpers, IntoConditionalSystem};
+use layout::DocumentLayout;
+use menu::{
+    despawn_intro, despawn_title, press_menu_buttons, spawn_intro, spawn_sound_settings,
+    spawn_title, toggle_sound_panel, update_volume_labels,
+};
+use newspaper::{
+    despawn_newspaper, press_continue, reveal_front_pages, show_continue_button, spawn_newspaper,
 };
 use ordered_float::NotNan;
+use rand::Rng;
+use review::{despawn_review, spawn_review, time_decisions, DecisionLog};
+use save::{continue_game, delete_save, save_game, ContinueGame};
+use serde::{Deserialize, Serialize};
+use settings::{load_settings, save_settings};
+use stack::{restack, DeskStack};
+use stamp_press::{animate_stamp_presses, press_stamps, StampPressed};
+use stamp_zone::{fade_stamp_zone_warnings, warn_misplaced_stamps, StampMisplaced, StampZone};
+use transition::{
+    fade, spawn_fade_overlay, start_transitions, transitioning, RequestTransition, Transition,
+};
 
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
-        .add_loopless_state(GameState::Desk)
+        .add_asset::<DossierAsset>()
+        .init_asset_loader::<DossierLoader>()
+        .add_asset::<CampaignAsset>()
+        .init_asset_loader::<CampaignLoader>()
+        .add_loopless_state(GameState::Loading)
         .add_startup_system(setup)
-        .add_enter_system(GameState::Desk, spawn_dossier)
-        .add_enter_system(GameState::Desk, spawn_checklist)
+        .add_startup_system(spawn_fade_overlay)
+        .init_resource::<Transition>()
+        .add_event::<RequestTransition>()
+        .add_system(start_transitions)
+        .add_system(fade.after(start_transitions))
+        .add_startup_system(load_settings)
+        .add_system(save_settings)
+        .add_event::<PlaySound>()
+        .add_system(play_sounds)
+        .add_system(update_ambience_volume)
+        .add_system(
+            finish_loading
+                .run_in_state(GameState::Loading)
+                .run_if_not(transitioning),
+        )
+        .add_enter_system(GameState::Title, spawn_title)
+        .add_exit_system(GameState::Title, despawn_title)
+        .add_enter_system(GameState::Intro, spawn_intro)
+        .add_exit_system(GameState::Intro, despawn_intro)
+        .add_system(press_menu_buttons)
+        .add_system(update_volume_labels)
+        .add_event::<ContinueGame>()
+        .add_system(continue_game.run_in_state(GameState::Title))
+        .add_system(save_game)
+        .add_enter_system(GameState::Desk, spawn_current_dossier)
+        .add_enter_system(GameState::Desk, spawn_handbook)
         .add_enter_system(GameState::Desk, spawn_stamp)
-        .add_system(begin_being_dragged)
+        .add_enter_system(GameState::Desk, spawn_trays)
+        .add_enter_system(GameState::Desk, spawn_file_report_button)
+        .add_enter_system(GameState::Desk, start_ambience)
+        .add_enter_system(GameState::Desk, spawn_sound_settings)
+        .add_system(toggle_sound_panel.run_in_state(GameState::Desk))
+        .add_event::<DossierFiled>()
+        .add_event::<PickedUp>()
+        .add_event::<Dropped>()
+        // Nothing on screen can be picked up or moved while fading, but anything already held can
+        // still be let go of
+        .add_system(begin_being_dragged.run_if_not(transitioning).label(PickUp))
         .add_system(stop_being_dragged)
-        .add_system(drag)
-        .add_system(check_timer.run_in_state(GameState::Desk))
+        // Ordered from here, as a system behind a run condition can't be referred to by name
+        .add_system(
+            drag.run_if_not(transitioning)
+                .into_descriptor()
+                .before(keep_on_desk),
+        )
+        .add_system(rustle_picked_up)
+        .add_system(
+            turn_handbook_pages
+                .run_in_state(GameState::Desk)
+                .run_if_not(transitioning)
+                .before(PickUp),
+        )
+        .add_system(show_handbook_page.run_in_state(GameState::Desk))
+        .init_resource::<DeskBounds>()
+        .add_system(update_desk_bounds)
+        .add_system(keep_on_desk)
+        .init_resource::<DeskStack>()
+        .add_system(restack)
+        .add_event::<StampPressed>()
+        .add_system(
+            press_stamps
+                .run_in_state(GameState::Desk)
+                .run_if_not(transitioning),
+        )
+        .add_system(animate_stamp_presses.run_in_state(GameState::Desk))
+        .add_system(stamp_dossier.run_in_state(GameState::Desk))
+        .add_event::<StampMisplaced>()
+        .add_system(warn_misplaced_stamps.run_in_state(GameState::Desk))
+        .add_system(fade_stamp_zone_warnings.run_in_state(GameState::Desk))
+        .add_system(submit_to_outbox.run_in_state(GameState::Desk))
+        .add_system(take_back_dossier.run_in_state(GameState::Desk))
+        .add_system(press_file_report.run_in_state(GameState::Desk))
+        .add_system(update_file_report_button.run_in_state(GameState::Desk))
+        .add_system(confirm_submissions.run_in_state(GameState::Desk))
+        .add_system(file_dossier.run_in_state(GameState::Desk))
+        .add_system(remember_stamp.run_in_state(GameState::Desk))
+        .init_resource::<DecisionLog>()
+        .add_system(time_decisions.run_in_state(GameState::Desk))
         .add_exit_system(GameState::Desk, despawn_desk)
+        .add_exit_system(GameState::Desk, stop_ambience)
         .add_enter_system(GameState::Newspaper, spawn_newspaper)
+        .add_enter_system(GameState::Newspaper, play_newspaper_sting)
+        .add_system(reveal_front_pages.run_in_state(GameState::Newspaper))
+        .add_system(show_continue_button.run_in_state(GameState::Newspaper))
+        .add_system(
+            press_continue
+                .run_in_state(GameState::Newspaper)
+                .run_if_not(transitioning),
+        )
+        .add_exit_system(GameState::Newspaper, despawn_newspaper)
+        .add_enter_system(GameState::Fired, spawn_letter)
+        .add_exit_system(GameState::Fired, despawn_letter)
+        .add_enter_system(GameState::Review, spawn_review)
+        .add_exit_system(GameState::Review, despawn_review)
         .add_system(calc_mouse_pos)
         .run();
 }
 
 fn despawn_desk(query: Query<Entity, With<OnDesk>>, mut commands: Commands) {
     for entity in &query {
-        commands.entity(entity).despawn();
+        commands.entity(entity).despawn_recursive();
     }
 }
 
-fn spawn_newspaper(mut commands: Commands, asset_server: Res<AssetServer>, windows: Res<Windows>) {
-    commands.spawn(SpriteBundle {
-        texture: asset_server.load("NYTimes-Edited.png"),
-        sprite: Sprite {
-            custom_size: Some(Vec2::new(
-                windows.primary().width(),
-                windows.primary().height(),
-            )),
-            ..default()
-        },
-        // transform: Transform::from_xyz(
-        //     // Horizontally centered on the page
-        //     paper_position.x,
-        //     paper_position.y + paper_size.y / 2.0 - logo_size.y / 2.0,
-        //     1.0,
-        // ),
-        ..default()
-    });
-}
-
 #[derive(Component)]
 struct OnDesk;
 
@@ -114,110 +235,70 @@ struct OnDesk;
 #[derive(Component)]
 struct MainCamera;
 
-fn spawn_checklist(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let paper_size = Vec2::new(300.0, 300.0 * 11.0 / 8.5);
-    let paper_position = Vec2::new(250.0, 100.0);
-    let logo_size = Vec2::new(40.0, 40.0);
-    let mono_font = asset_server.load("fonts/FiraMono-Medium.ttf");
-    let instructions_offset = Vec2::new(0.0, -logo_size.y - 44.0);
-    let header_offset = Vec2::new(0.0, -logo_size.y);
-    let paper_top_left = Vec2::new(
-        paper_position.x - paper_size.x / 2.0,
-        paper_position.y + paper_size.y / 2.0,
-    );
-    let paper_top_middle = Vec2::new(paper_position.x, paper_top_left.y);
-
-    let logo = commands
-        .spawn((
-            SpriteBundle {
-                texture: asset_server.load("cia.png"),
-                sprite: Sprite {
-                    custom_size: Some(logo_size),
-                    ..default()
-                },
-                transform: Transform::from_xyz(
-                    // Horizontally centered on the page
-                    paper_position.x,
-                    paper_position.y + paper_size.y / 2.0 - logo_size.y / 2.0,
-                    21.0,
-                ),
-                ..default()
-            },
-            OnDesk,
-        ))
-        .id();
-
-    let text_header = commands
-        .spawn((
-            Text2dBundle {
-                text: Text::from_section(
-                    "Analyst Handbook\nChapter 10: Intervention Policy".to_owned(),
-                    TextStyle {
-                        font: mono_font.clone(),
-                        font_size: 18.0,
-                        color: Color::BLACK,
-                    },
-                )
-                .with_alignment(TextAlignment::TOP_CENTER),
-                transform: Transform::from_translation(
-                    (paper_top_middle + header_offset).extend(21.0),
-                ),
-                ..default()
-            },
-            OnDesk,
-        ))
-        .id();
-
-    let text_instructions = commands
-        .spawn((
-            Text2dBundle {
-                text: Text::from_section(
-                    "  1. Assassinate if interfering with U.S. corporate involvement or oil import
-  2. Assassinate if providing high quality of life to citizens
-  3. If constituents demand replacement, select suitable replacement, then perform coup"
-                        .to_owned(),
-                    TextStyle {
-                        font: mono_font,
-                        font_size: 14.0,
-                        color: Color::BLACK,
-                    },
-                ),
-                text_2d_bounds: Text2dBounds {
-                    size: Vec2::new(
-                        paper_size.x - instructions_offset.x,
-                        paper_size.y + instructions_offset.y,
-                    ),
-                },
-                transform: Transform::from_translation(
-                    (paper_top_left + instructions_offset).extend(21.0),
-                ),
-                ..default()
-            },
-            OnDesk,
-        ))
-        .id();
+/// The assets the papers on the desk are printed from
+#[derive(SystemParam)]
+struct DeskAssets<'w, 's> {
+    asset_server: Res<'w, AssetServer>,
+    dossiers: Res<'w, Assets<DossierAsset>>,
+    images: Res<'w, Assets<Image>>,
+    #[system_param(ignore)]
+    marker: PhantomData<&'s ()>,
+}
 
-    commands.spawn((
-        SpriteBundle {
-            sprite: Sprite {
-                color: Color::WHITE,
-                custom_size: Some(paper_size),
-                ..default()
-            },
-            transform: Transform::from_translation(paper_position.extend(20.0)),
-            ..default()
-        },
-        DragHitBox {
-            size: paper_size,
-            members: vec![text_header, text_instructions, logo],
-        },
-        OnDesk,
-    ));
+fn spawn_current_dossier(mut commands: Commands, assets: DeskAssets, campaign: Res<Campaign>) {
+    let Some(handle) = campaign.current_dossier() else {
+        return;
+    };
+    let Some(recommendation) = campaign.stamped() else {
+        spawn_dossier(
+            &mut commands,
+            &assets.asset_server,
+            handle.clone(),
+            assets.dossiers.get(handle).unwrap(),
+            DecisionState::Undecided,
+        );
+        return;
+    };
+    // Stamped before the analyst last left the desk, the mark is put back where it was
+    let paper = spawn_dossier(
+        &mut commands,
+        &assets.asset_server,
+        handle.clone(),
+        assets.dossiers.get(handle).unwrap(),
+        DecisionState::Stamped(recommendation),
+    );
+    let stamp = Stamp {
+        kind: recommendation.stamp,
+        mark: assets.asset_server.load(recommendation.stamp.stamped_art()),
+    };
+    let stamped = Transform::from_translation(recommendation.position.extend(0.0))
+        .with_rotation(Quat::from_rotation_z(recommendation.rotation));
+    let part = stamped_within(&stamped, Rect::from_center_size(Vec2::ZERO, DOSSIER_SIZE));
+    commands.entity(paper).with_children(|paper| {
+        spawn_stamped(
+            paper,
+            &assets.asset_server,
+            &assets.images,
+            &stamp,
+            stamped,
+            part,
+        );
+    });
 }
 
-fn spawn_dossier(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let paper_size = Vec2::new(350.0, 350.0 * 11.0 / 8.5);
-    let paper_position = Vec2::new(-150.0, 50.0);
+/// Size of a dossier's paper
+const DOSSIER_SIZE: Vec2 = Vec2::new(350.0, 350.0 * 11.0 / 8.5);
+
+fn spawn_dossier(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    handle: Handle<DossierAsset>,
+    dossier: &DossierAsset,
+    decision: DecisionState,
+) -> Entity {
+    let paper_size = DOSSIER_SIZE;
+    // New dossiers arrive in the inbox
+    let paper_position = INBOX_POSITION;
 
     // Dossier - the description of the person
     // - Name
@@ -227,351 +308,761 @@ fn spawn_dossier(mut commands: Commands, asset_server: Res<AssetServer>) {
     // - A spot for your stamp for your decision
 
     let logo_size = Vec2::new(80.0, 80.0);
-
-    let logo = commands
-        .spawn((
-            SpriteBundle {
-                texture: asset_server.load("cia.png"),
-                sprite: Sprite {
-                    custom_size: Some(logo_size),
-                    ..default()
-                },
-                transform: Transform::from_xyz(
-                    paper_position.x - paper_size.x / 2.0 + logo_size.x / 2.0,
-                    paper_position.y + paper_size.y / 2.0 - logo_size.y / 2.0,
-                    41.0,
-                ),
-                ..default()
-            },
-            OnDesk,
-        ))
-        .id();
-
     let headshot_size = Vec2::new(90.0, 90.0 * 11.0 / 8.5);
-
+    let stamp_box_size = Vec2::new(240.0, 100.0);
     let margin = 4.0;
-
     let mono_font = asset_server.load("fonts/FiraMono-Medium.ttf");
 
-    let headshot = commands
-        .spawn((
-            SpriteBundle {
-                texture: asset_server.load("nasser.png"),
-                sprite: Sprite {
-                    custom_size: Some(headshot_size),
-                    ..default()
-                },
-                transform: Transform::from_xyz(
-                    paper_position.x - paper_size.x / 2.0 + headshot_size.x / 2.0,
-                    paper_position.y + paper_size.y / 2.0
-                        - logo_size.y
-                        - headshot_size.y / 2.0
-                        - margin,
-                    41.0,
-                ),
-                ..default()
-            },
-            OnDesk,
-        ))
-        .id();
-
-    let text_confidential = commands
-        .spawn((
-            Text2dBundle {
-                text: Text::from_section(
-                    "DO NOT COPY/CONFIDENTIAL\nForeign Leader Report".to_owned(),
-                    TextStyle {
-                        font: mono_font.clone(),
-                        font_size: 18.0,
-                        color: Color::BLACK,
-                    },
-                ),
-                transform: Transform::from_xyz(
-                    paper_position.x - paper_size.x / 2.0 + logo_size.x + margin,
-                    paper_position.y + paper_size.y / 2.0 - margin,
-                    41.0,
-                ),
-                ..default()
-            },
-            OnDesk,
-        ))
-        .id();
-
-    let text_facts = commands
-        .spawn((
-            Text2dBundle {
-                text: Text::from_section(
-                    "\
-Name: Gamal Abdel Nasser
-Title: President of Egypt
-D.O.B.: 15 January 1918
-Gender: Male
-Nationality: Egypt
-Constituency: Loyal
-Eye Color: Brown
-Hair Color: Black"
-                        .to_owned(),
-                    TextStyle {
-                        font: mono_font.clone(),
-                        font_size: 14.0,
-                        color: Color::BLACK,
-                    },
-                ),
-                transform: Transform::from_xyz(
-                    paper_position.x - paper_size.x / 2.0 + headshot_size.x + margin,
-                    paper_position.y + paper_size.y / 2.0 - logo_size.y,
-                    41.0,
-                ),
-                ..default()
-            },
-            OnDesk,
-        ))
-        .id();
-
-    let text_policies = commands
-        .spawn((
-            Text2dBundle {
-                text: Text::from_section(
-                    "\
-Policies:
-• Universal Health Care
-• Free education
-• Redistributes land to small farmers
-• Nationalizes local industry
-• No foreign corporations in Egypt
-• Interefered with global oil import"
-                        .to_owned(),
-                    TextStyle {
-                        font: mono_font.clone(),
-                        font_size: 14.0,
-                        color: Color::BLACK,
-                    },
-                ),
-                transform: Transform::from_xyz(
-                    paper_position.x - paper_size.x / 2.0 + margin,
-                    paper_position.y + paper_size.y / 2.0
-                        - logo_size.y
-                        - headshot_size.y
-                        - margin * 2.0,
-                    41.0,
-                ),
-                ..default()
-            },
-            OnDesk,
-        ))
-        .id();
-
-    let stamped_size_y = 100.0;
-    let text_stamp_label = commands
-        .spawn((
-            Text2dBundle {
-                text: Text::from_section(
-                    "Analyst Recommendation:\n(Place Stamp Below)".to_owned(),
-                    TextStyle {
-                        font: mono_font,
-                        font_size: 15.0,
-                        color: Color::BLACK,
-                    },
-                )
-                .with_alignment(TextAlignment::BOTTOM_CENTER),
-                transform: Transform::from_xyz(
-                    paper_position.x,
-                    paper_position.y - paper_size.y / 2.0 + stamped_size_y + margin,
-                    41.0,
-                ),
-                ..default()
-            },
-            OnDesk,
-        ))
-        .id();
-
-    // One option
-    // Just have all the parts of the dossier as separate entities with a shared component and iterate over them when starting to drag
-    commands.spawn((
+    let mut paper = commands.spawn((
         SpriteBundle {
             sprite: Sprite {
                 color: Color::WHITE,
                 custom_size: Some(paper_size),
                 ..default()
             },
-            transform: Transform::from_translation(paper_position.extend(40.0)),
+            transform: Transform::from_translation(paper_position.extend(40.0))
+                .with_rotation(Quat::from_rotation_z(0.02)),
             ..default()
         },
         DragHitBox {
             size: paper_size,
-            members: vec![
-                text_facts,
-                logo,
-                headshot,
-                text_confidential,
-                text_stamp_label,
-                text_policies,
-            ],
+            kind: DragKind::Dossier,
+            alpha_mask: false,
+        },
+        Dossier {
+            asset: handle,
+            decision,
         },
-        Dossier,
         OnDesk,
     ));
+    let mut stamp_zone = Rect::default();
+    paper.with_children(|paper| {
+        let mut layout = DocumentLayout::new(paper, paper_size, margin, mono_font);
+        layout.image_with_text(
+            asset_server.load("cia.png"),
+            logo_size,
+            "DO NOT COPY/CONFIDENTIAL\nForeign Leader Report",
+            18.0,
+        );
+        layout.image_with_text(
+            asset_server.load(dossier.photo.as_str()),
+            headshot_size,
+            &dossier.facts_text(),
+            14.0,
+        );
+        layout.paragraph("Policies:", 14.0);
+        layout.bullets(
+            dossier.policies.iter().map(|policy| policy.text.as_str()),
+            14.0,
+        );
+        stamp_zone = layout.stamp_box(
+            "Analyst Recommendation:\n(Right Click Stamp Below)",
+            15.0,
+            stamp_box_size,
+        );
+    });
+    paper.insert(StampZone { rect: stamp_zone });
+    paper.id()
 }
 
-fn setup(mut commands: Commands) {
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
     commands.init_resource::<Mouse>();
-    commands.init_resource::<StampStatus>();
+    commands.insert_resource(CampaignHandle(asset_server.load("main.campaign.ron")));
+    commands.insert_resource(StampMarks(
+        StampKind::ALL
+            .iter()
+            .map(|kind| asset_server.load(kind.stamped_art()))
+            .collect(),
+    ));
     commands.spawn((Camera2dBundle::default(), MainCamera));
 }
 
+/// The campaign being loaded, before it's turned into a [`Campaign`]
+#[derive(Resource)]
+struct CampaignHandle(Handle<CampaignAsset>);
+
+/// Every stamp's mark, held for the whole game so marks can always be cropped to the paper
+#[derive(Resource)]
+struct StampMarks(Vec<Handle<Image>>);
+
+fn finish_loading(
+    asset_server: Res<AssetServer>,
+    campaign_handle: Res<CampaignHandle>,
+    campaigns: Res<Assets<CampaignAsset>>,
+    marks: Res<StampMarks>,
+    mut transitions: EventWriter<RequestTransition>,
+    mut commands: Commands,
+) {
+    match asset_server.get_group_load_state(marks.0.iter().map(|mark| mark.id())) {
+        LoadState::Loaded => {}
+        LoadState::Failed => panic!("failed to load stamp marks"),
+        _ => return,
+    }
+    match asset_server.get_load_state(&campaign_handle.0) {
+        LoadState::Loaded => {}
+        LoadState::Failed => panic!("failed to load campaign"),
+        _ => return,
+    }
+    let campaign = campaigns.get(&campaign_handle.0).unwrap();
+    let dossiers = campaign.days.iter().flatten().map(|handle| handle.id());
+    match asset_server.get_group_load_state(dossiers) {
+        LoadState::Loaded => {
+            commands.insert_resource(Campaign::new(campaign));
+            commands.insert_resource(campaign.handbook.clone());
+            transitions.send(RequestTransition(GameState::Title));
+        }
+        LoadState::Failed => panic!("failed to load dossiers"),
+        _ => {}
+    }
+}
+
 fn spawn_stamp(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let stamp_size = Vec2::new(150.0, 150.0);
-    let stamped_size = Vec2::new(150.0, 100.0);
-    commands.spawn((
-        SpriteBundle {
-            texture: asset_server.load("AssassinateStamp.png"),
+    for (i, kind) in StampKind::ALL.into_iter().enumerate() {
+        let position = Vec3::new(160.0 + 160.0 * i as f32, -250.0, 61.0);
+        match kind.art() {
+            Some((stamp_art, _)) => {
+                let stamp_size = Vec2::new(150.0, 150.0);
+                commands.spawn((
+                    SpriteBundle {
+                        texture: asset_server.load(stamp_art),
+                        sprite: Sprite {
+                            custom_size: Some(stamp_size),
+                            ..default()
+                        },
+                        transform: Transform::from_translation(position),
+                        ..default()
+                    },
+                    DragHitBox {
+                        size: stamp_size,
+                        kind: DragKind::Stamp,
+                        alpha_mask: true,
+                    },
+                    Stamp {
+                        kind,
+                        mark: asset_server.load(kind.stamped_art()),
+                    },
+                    OnDesk,
+                ));
+            }
+            // No art yet, so make do with a wooden block with the label on it
+            None => {
+                let stamp_size = Vec2::new(150.0, 60.0);
+                commands
+                    .spawn((
+                        SpriteBundle {
+                            sprite: Sprite {
+                                color: Color::rgb(0.4, 0.25, 0.1),
+                                custom_size: Some(stamp_size),
+                                ..default()
+                            },
+                            transform: Transform::from_translation(position),
+                            ..default()
+                        },
+                        DragHitBox {
+                            size: stamp_size,
+                            kind: DragKind::Stamp,
+                            alpha_mask: false,
+                        },
+                        Stamp {
+                            kind,
+                            mark: asset_server.load(kind.stamped_art()),
+                        },
+                        OnDesk,
+                    ))
+                    .with_children(|block| {
+                        block.spawn(Text2dBundle {
+                            text: Text::from_section(
+                                kind.label(),
+                                TextStyle {
+                                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                                    font_size: 24.0,
+                                    color: Color::RED,
+                                },
+                            )
+                            .with_alignment(TextAlignment::CENTER),
+                            transform: Transform::from_xyz(0.0, 0.0, 1.0),
+                            ..default()
+                        });
+                    });
+            }
+        }
+    }
+}
+
+/// Spawns the mark left by stamping on the paper, placed relative to the paper. Only `part` of
+/// the mark, the bit that's on the paper relative to the mark itself, is drawn
+fn spawn_stamped(
+    paper: &mut ChildBuilder,
+    asset_server: &AssetServer,
+    images: &Assets<Image>,
+    stamp: &Stamp,
+    transform: Transform,
+    part: Rect,
+) {
+    // Marks are loaded before the game starts, but a missing one is still drawn, just whole
+    let crop = images.get(&stamp.mark).map(|image| {
+        let texture_size = image.size();
+        let uv = |point: Vec2| {
+            Vec2::new(
+                point.x / STAMPED_SIZE.x + 0.5,
+                0.5 - point.y / STAMPED_SIZE.y,
+            )
+        };
+        Rect::from_corners(
+            uv(Vec2::new(part.min.x, part.max.y)) * texture_size,
+            uv(Vec2::new(part.max.x, part.min.y)) * texture_size,
+        )
+    });
+    let (size, offset) = match crop {
+        Some(_) => (part.size(), part.center()),
+        None => (STAMPED_SIZE, Vec2::ZERO),
+    };
+    // A label can't be cut, so it only goes on a mark that has all of it on the paper
+    let label = stamp.kind.label();
+    let label_size = Vec2::new(
+        (label.chars().count() as f32 * STAMPED_LABEL_SIZE * 0.6).min(STAMPED_SIZE.x),
+        STAMPED_LABEL_SIZE * 1.2,
+    );
+    let label_fits = part
+        .intersect(Rect::from_center_size(Vec2::ZERO, label_size))
+        .size()
+        == label_size;
+
+    // No two stamps leave quite the same amount of ink
+    let mut rng = rand::thread_rng();
+    let ink = rng.gen_range(STAMP_MIN_INK..=1.0);
+    let shade = rng.gen_range(0.85..=1.0);
+
+    paper
+        .spawn(SpriteBundle {
+            texture: stamp.mark.clone(),
             sprite: Sprite {
-                custom_size: Some(stamp_size),
+                color: Color::rgba(shade, shade, shade, ink),
+                custom_size: Some(size),
+                rect: crop,
                 ..default()
             },
-            transform: Transform::from_xyz(400.0, -200.0, 61.0),
+            // Above everything printed on the paper
+            transform: Transform {
+                translation: (transform.translation + transform.rotation * offset.extend(0.0))
+                    .truncate()
+                    .extend(20.0),
+                ..transform
+            },
             ..default()
-        },
-        DragHitBox {
-            size: stamp_size,
-            members: vec![],
-        },
-        Stamp {
-            stamped_sprite: SpriteBundle {
-                texture: asset_server.load("AssassinateStamped.png"),
-                sprite: Sprite {
-                    custom_size: Some(stamped_size),
+        })
+        .with_children(|mark| {
+            if stamp.kind.art().is_none() && label_fits {
+                mark.spawn(Text2dBundle {
+                    text: Text::from_section(
+                        label,
+                        TextStyle {
+                            font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                            font_size: STAMPED_LABEL_SIZE,
+                            color: Color::rgba(0.75, 0.1, 0.1, ink),
+                        },
+                    )
+                    .with_alignment(TextAlignment::CENTER),
+                    // In the middle of the whole mark, wherever the part drawn is
+                    transform: Transform::from_translation((-offset).extend(0.5)),
                     ..default()
-                },
-                transform: Transform::from_xyz(0.0, -20.0, 60.0),
-                ..default()
-            },
-        },
-        OnDesk,
-    ));
+                });
+            }
+        });
 }
 
+/// A document or tool that can be picked up and dragged around by its root entity, along
+/// with its children
 #[derive(Debug, Component)]
 struct DragHitBox {
     size: Vec2,
-    members: Vec<Entity>,
+    kind: DragKind,
+    /// Only grab where the sprite's texture isn't transparent, so clicks on its see-through
+    /// parts reach whatever is underneath
+    alpha_mask: bool,
+}
+
+/// What's being dragged, for drop targets to pick what they accept
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DragKind {
+    Stamp,
+    Dossier,
+    Document,
+    Newspaper,
 }
 
 #[derive(Component)]
 struct BeingDragged;
 
+/// Somewhere on the desk things can be dropped
+#[derive(Component)]
+struct DropTarget {
+    size: Vec2,
+    accepts: &'static [DragKind],
+}
+
+/// Sent when a draggable is let go over the front-most drop target that accepts it
+struct Dropped {
+    dragged: Entity,
+    target: Entity,
+}
+
+/// Sent when a draggable is picked up
+struct PickedUp {
+    dragged: Entity,
+}
+
+/// Labels picking things up, for systems that need to get to a click first
+#[derive(SystemLabel)]
+struct PickUp;
+
+/// The draggable things the mouse is over
+#[derive(SystemParam)]
+struct UnderMouse<'w, 's> {
+    mouse: Res<'w, Mouse>,
+    draggables: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static DragHitBox,
+            &'static GlobalTransform,
+            Option<&'static Handle<Image>>,
+        ),
+    >,
+    images: Res<'w, Assets<Image>>,
+}
+
+impl UnderMouse<'_, '_> {
+    /// The front-most thing under the mouse, the one a click lands on
+    fn front(&self) -> Option<Entity> {
+        self.draggables
+            .iter()
+            .filter(|&(_, hitbox, transform, texture)| {
+                hovers(hitbox, transform, texture, &self.images, &self.mouse)
+            })
+            .max_by_key(|(_, _, transform, _)| NotNan::new(transform.translation().z).unwrap())
+            .map(|(entity, ..)| entity)
+    }
+}
+
 fn begin_being_dragged(
     input: Res<Input<MouseButton>>,
-    mouse: Res<Mouse>,
-    query: Query<(Entity, &DragHitBox, &GlobalTransform, Option<&Stamp>)>,
+    under_mouse: UnderMouse,
+    mut picked_up: EventWriter<PickedUp>,
     mut commands: Commands,
     mut windows: ResMut<Windows>,
-    mut stamp_status: ResMut<StampStatus>,
 ) {
     if input.just_pressed(MouseButton::Left) {
-        let front_clicked_entity = query
-            .iter()
-            .filter(|&(_, hitbox, transform, _)| hovers(hitbox, transform, &mouse))
-            .max_by_key(|(_, _, transform, _)| NotNan::new(transform.translation().z).unwrap());
-
-        if let Some((entity, hitbox, _, stamp)) = front_clicked_entity {
+        if let Some(entity) = under_mouse.front() {
             windows
                 .get_primary_mut()
                 .unwrap()
                 .set_cursor_icon(CursorIcon::Grabbing);
             commands.entity(entity).insert(BeingDragged);
-            for &member in &hitbox.members {
-                commands.entity(member).insert(BeingDragged);
-            }
+            picked_up.send(PickedUp { dragged: entity });
+        }
+    }
+}
 
-            if matches!(*stamp_status, StampStatus::Dropped) && stamp.is_some() {
-                dbg!("picked_up");
-                *stamp_status = StampStatus::PickedUp(Timer::from_seconds(5.0, TimerMode::Once))
-            }
+fn hovers(
+    hitbox: &DragHitBox,
+    transform: &GlobalTransform,
+    texture: Option<&Handle<Image>>,
+    images: &Assets<Image>,
+    mouse: &Mouse,
+) -> bool {
+    if !contains(transform, hitbox.size, mouse.position) {
+        return false;
+    }
+    match texture.and_then(|texture| images.get(texture)) {
+        Some(image) if hitbox.alpha_mask => {
+            // Texture rows go from the top down
+            let local = to_local(transform, mouse.position) / hitbox.size;
+            opaque_at(image, Vec2::new(local.x + 0.5, 0.5 - local.y))
         }
+        _ => true,
     }
 }
 
-fn hovers(hitbox: &DragHitBox, transform: &GlobalTransform, mouse: &Mouse) -> bool {
-    mouse.position.x > transform.translation().x - hitbox.size.x * 0.5
-        && mouse.position.x < transform.translation().x + hitbox.size.x * 0.5
-        && mouse.position.y > transform.translation().y - hitbox.size.y * 0.5
-        && mouse.position.y < transform.translation().y + hitbox.size.y * 0.5
+/// Alpha below which a pixel counts as see-through
+const ALPHA_THRESHOLD: u8 = 16;
+
+/// Whether the texture has something drawn at a point, from (0, 0) at its top left to (1, 1)
+/// at its bottom right
+fn opaque_at(image: &Image, uv: Vec2) -> bool {
+    // Only 8-bit RGBA can be sampled, anything else is treated as solid
+    if !matches!(
+        image.texture_descriptor.format,
+        TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb
+    ) {
+        return true;
+    }
+    let size = image.texture_descriptor.size;
+    let x = ((uv.x * size.width as f32) as u32).min(size.width - 1);
+    let y = ((uv.y * size.height as f32) as u32).min(size.height - 1);
+    let pixel = (y * size.width + x) as usize * 4;
+    image.data[pixel + 3] > ALPHA_THRESHOLD
+}
+
+/// Whether a point in the world is on something of the given size, however it's been moved,
+/// rotated or scaled
+fn contains(transform: &GlobalTransform, size: Vec2, point: Vec2) -> bool {
+    Rect::from_center_size(Vec2::ZERO, size).contains(to_local(transform, point))
+}
+
+/// Takes a point in the world into the space of something on the desk, where its center is
+/// the origin
+fn to_local(transform: &GlobalTransform, point: Vec2) -> Vec2 {
+    transform
+        .affine()
+        .inverse()
+        .transform_point3(point.extend(transform.translation().z))
+        .truncate()
 }
 
 #[derive(Component)]
 struct Stamp {
-    stamped_sprite: SpriteBundle,
+    kind: StampKind,
+    /// Texture of the mark it leaves
+    mark: Handle<Image>,
+}
+
+/// The decisions an analyst can stamp on a dossier
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum StampKind {
+    Assassinate,
+    Coup,
+    Respect,
 }
 
+impl StampKind {
+    pub const ALL: [StampKind; 3] = [StampKind::Assassinate, StampKind::Coup, StampKind::Respect];
+
+    fn verb(self) -> &'static str {
+        match self {
+            StampKind::Assassinate => "assassinate",
+            StampKind::Coup => "overthrow",
+            StampKind::Respect => "respect",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            StampKind::Assassinate => "ASSASSINATE",
+            StampKind::Coup => "COUP",
+            StampKind::Respect => "RESPECT",
+        }
+    }
+
+    /// Textures for the stamp and the mark it leaves, if they've been drawn
+    fn art(self) -> Option<(&'static str, &'static str)> {
+        match self {
+            StampKind::Assassinate => Some(("AssassinateStamp.png", "AssassinateStamped.png")),
+            StampKind::Coup | StampKind::Respect => None,
+        }
+    }
+
+    /// Texture of the mark it leaves, a blank one with the label over it if there's no art
+    fn stamped_art(self) -> &'static str {
+        match self.art() {
+            Some((_, stamped_art)) => stamped_art,
+            None => "EmptyStamp.jpg",
+        }
+    }
+}
+
+/// Size of the mark a stamp leaves on paper
+const STAMPED_SIZE: Vec2 = Vec2::new(150.0, 100.0);
+/// Font size of the label on a blank mark
+const STAMPED_LABEL_SIZE: f32 = 40.0;
+/// Where the mark lands relative to the stamp
+const STAMPED_OFFSET: Vec2 = Vec2::new(0.0, -20.0);
+/// How far askew a mark can land, in radians
+const STAMP_MAX_TILT: f32 = 0.12;
+/// Least opacity of a mark, for a stamp that's running dry
+const STAMP_MIN_INK: f32 = 0.7;
+/// How much of a mark has to be in the recommendation box for the recommendation to count
+const STAMP_MOSTLY_INSIDE: f32 = 0.6;
+
 #[derive(Component)]
-struct Dossier;
+struct Dossier {
+    asset: Handle<DossierAsset>,
+    decision: DecisionState,
+}
+
+/// Where the trays sit on the desk
+const INBOX_POSITION: Vec2 = Vec2::new(-470.0, 160.0);
+const OUTBOX_POSITION: Vec2 = Vec2::new(-470.0, -200.0);
+const TRAY_SIZE: Vec2 = Vec2::new(300.0, 280.0);
+
+#[derive(Component)]
+struct Inbox;
+
+#[derive(Component)]
+struct Outbox;
+
+fn spawn_trays(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let inbox = spawn_tray(&mut commands, &asset_server, INBOX_POSITION, "INBOX");
+    commands.entity(inbox).insert(Inbox);
+    let outbox = spawn_tray(&mut commands, &asset_server, OUTBOX_POSITION, "OUTBOX");
+    commands.entity(outbox).insert((
+        Outbox,
+        DropTarget {
+            size: TRAY_SIZE,
+            accepts: &[DragKind::Dossier],
+        },
+    ));
+}
+
+fn spawn_tray(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    position: Vec2,
+    label: &str,
+) -> Entity {
+    commands.spawn((
+        Text2dBundle {
+            text: Text::from_section(
+                label,
+                TextStyle {
+                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                    font_size: 24.0,
+                    color: Color::rgb(0.8, 0.7, 0.5),
+                },
+            )
+            .with_alignment(TextAlignment::BOTTOM_CENTER),
+            transform: Transform::from_translation(
+                (position - Vec2::new(0.0, TRAY_SIZE.y / 2.0 - 8.0)).extend(2.0),
+            ),
+            ..default()
+        },
+        OnDesk,
+    ));
 
-#[derive(Default, Resource)]
-enum StampStatus {
-    #[default]
-    Initial,
-    Dropped,
-    PickedUp(Timer),
+    commands
+        .spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: Color::rgb(0.3, 0.2, 0.1),
+                    custom_size: Some(TRAY_SIZE),
+                    ..default()
+                },
+                transform: Transform::from_translation(position.extend(1.0)),
+                ..default()
+            },
+            OnDesk,
+        ))
+        .id()
 }
 
 fn stop_being_dragged(
     input: Res<Input<MouseButton>>,
-    being_dragged: Query<(Entity, Option<(&Stamp, &Transform)>), With<BeingDragged>>,
-    mut dossier: Query<(&mut DragHitBox, &Transform), With<Dossier>>,
+    being_dragged: Query<(Entity, &DragHitBox, &GlobalTransform), With<BeingDragged>>,
+    targets: Query<(Entity, &DropTarget, &GlobalTransform)>,
+    mut dropped: EventWriter<Dropped>,
+    mut sounds: EventWriter<PlaySound>,
     mut commands: Commands,
     mut windows: ResMut<Windows>,
-    mut stamp_status: ResMut<StampStatus>,
 ) {
     if input.just_released(MouseButton::Left) {
         windows
             .get_primary_mut()
             .unwrap()
             .set_cursor_icon(CursorIcon::Default);
-        for (entity, stamp) in &being_dragged {
+        for (entity, hitbox, transform) in &being_dragged {
             commands.entity(entity).remove::<BeingDragged>();
+            sounds.send(PlaySound(Sound::Drop));
 
-            // Dropping the stamp means stamping it
-            if let Some((stamp, stamp_transform)) = stamp {
-                let mut stamped_sprite = stamp.stamped_sprite.clone();
-                stamped_sprite.transform.translation.x += stamp_transform.translation.x;
-                stamped_sprite.transform.translation.y += stamp_transform.translation.y;
-
-                let (mut hitbox, dossier_transform) = dossier.single_mut();
-                if stamp_fits(
-                    stamp,
-                    &stamped_sprite.transform,
-                    dossier_transform,
-                    hitbox.size,
-                ) {
-                    let id = commands.spawn((stamped_sprite, OnDesk)).id();
-                    hitbox.members.push(id);
-
-                    if matches!(*stamp_status, StampStatus::Initial) {
-                        *stamp_status = StampStatus::Dropped;
-                    }
-                }
+            let front_target = front_drop_target(
+                &targets,
+                entity,
+                hitbox.kind,
+                transform.translation().truncate(),
+            );
+            if let Some(target) = front_target {
+                dropped.send(Dropped {
+                    dragged: entity,
+                    target,
+                });
             }
         }
     }
 }
 
-fn stamp_fits(
-    stamp: &Stamp,
-    stamped_transform: &Transform,
-    dossier_transform: &Transform,
-    dossier_size: Vec2,
-) -> bool {
-    let stamped = Rect::from_center_size(
-        stamped_transform.translation.truncate(),
-        stamp.stamped_sprite.sprite.custom_size.unwrap(),
+/// The front-most drop target at a position that accepts the kind of thing, other than the
+/// thing itself
+fn front_drop_target(
+    targets: &Query<(Entity, &DropTarget, &GlobalTransform)>,
+    entity: Entity,
+    kind: DragKind,
+    position: Vec2,
+) -> Option<Entity> {
+    targets
+        .iter()
+        .filter(|&(target, drop_target, target_transform)| {
+            target != entity
+                && drop_target.accepts.contains(&kind)
+                && contains(target_transform, drop_target.size, position)
+        })
+        .max_by_key(|(_, _, transform)| NotNan::new(transform.translation().z).unwrap())
+        .map(|(target, _, _)| target)
+}
+
+/// Pressing a stamp on a dossier leaves its mark on whatever part of it lands on the paper.
+/// The recommendation only counts if the mark is mostly in the recommendation box, and a
+/// mark anywhere else leaves the dossier undecided
+fn stamp_dossier(
+    mut pressed: EventReader<StampPressed>,
+    draggables: Query<(Entity, &DragHitBox, &GlobalTransform, Option<&Stamp>)>,
+    mut dossiers: Query<(&DragHitBox, &StampZone, &GlobalTransform, &mut Dossier)>,
+    mut misplaced: EventWriter<StampMisplaced>,
+    mut sounds: EventWriter<PlaySound>,
+    mut commands: Commands,
+    assets: DeskAssets,
+) {
+    for &StampPressed { stamp: entity } in pressed.iter() {
+        let Ok((_, _, stamp_transform, Some(stamp))) = draggables.get(entity) else {
+            continue;
+        };
+        // The mark goes on whatever is front-most under the stamp, so only if that's a dossier
+        let position = stamp_transform.translation().truncate();
+        let Some(target) = draggables
+            .iter()
+            .filter(|&(other, hitbox, transform, _)| {
+                other != entity && contains(transform, hitbox.size, position)
+            })
+            .max_by_key(|(_, _, transform, _)| NotNan::new(transform.translation().z).unwrap())
+            .map(|(target, ..)| target)
+        else {
+            continue;
+        };
+        let Ok((hitbox, zone, dossier_transform, mut dossier)) = dossiers.get_mut(target) else {
+            continue;
+        };
+        let mut stamped = stamped_transform(stamp_transform, dossier_transform);
+        // Nobody stamps perfectly straight
+        stamped.rotate_z(rand::thread_rng().gen_range(-STAMP_MAX_TILT..=STAMP_MAX_TILT));
+
+        let on_paper = stamped_within(&stamped, Rect::from_center_size(Vec2::ZERO, hitbox.size));
+        if on_paper.is_empty() {
+            continue;
+        }
+        // The mark sticks to the paper wherever it's dragged next
+        commands.entity(target).with_children(|paper| {
+            spawn_stamped(
+                paper,
+                &assets.asset_server,
+                &assets.images,
+                stamp,
+                stamped,
+                on_paper,
+            );
+        });
+        sounds.send(PlaySound(Sound::Stamp));
+        if coverage(stamped_within(&stamped, zone.rect)) < STAMP_MOSTLY_INSIDE {
+            misplaced.send(StampMisplaced { dossier: target });
+            dossier.decision.spoil();
+        } else {
+            dossier.decision.restamp(Recommendation {
+                stamp: stamp.kind,
+                position: stamped.translation.truncate(),
+                rotation: stamped.rotation.to_euler(EulerRot::XYZ).2,
+            });
+        }
+    }
+}
+
+/// Keeps the campaign up to date with what's stamped on the current dossier, so the stamp is
+/// still there after quitting and continuing
+fn remember_stamp(dossiers: Query<&Dossier, Changed<Dossier>>, mut campaign: ResMut<Campaign>) {
+    for dossier in &dossiers {
+        // A dossier that's just been filed is no longer the current one
+        if campaign.current_dossier() != Some(&dossier.asset) {
+            continue;
+        }
+        let recommendation = dossier.decision.recommendation();
+        // Only an actual change is saved, not every tick of the undo window
+        if campaign.stamped() != recommendation {
+            campaign.stamp_current(recommendation);
+        }
+    }
+}
+
+/// Dropping a stamped dossier in the outbox submits it
+fn submit_to_outbox(
+    mut dropped: EventReader<Dropped>,
+    outbox: Query<(), With<Outbox>>,
+    mut dossiers: Query<&mut Dossier>,
+) {
+    for &Dropped { dragged, target } in dropped.iter() {
+        if outbox.contains(target) {
+            if let Ok(mut dossier) = dossiers.get_mut(dragged) {
+                dossier.decision.submit();
+            }
+        }
+    }
+}
+
+/// Picking a submitted dossier back up takes it out of the outbox
+fn take_back_dossier(mut picked_up: EventReader<PickedUp>, mut dossiers: Query<&mut Dossier>) {
+    for &PickedUp { dragged } in picked_up.iter() {
+        if let Ok(mut dossier) = dossiers.get_mut(dragged) {
+            dossier.decision.cancel();
+        }
+    }
+}
+
+/// Where the mark a stamp would leave goes on the paper, relative to the paper
+fn stamped_transform(
+    stamp_transform: &GlobalTransform,
+    dossier_transform: &GlobalTransform,
+) -> Transform {
+    // The mark is as big as the stamp, however squashed the stamp is while pressing
+    let stamp_transform = GlobalTransform::from(Transform {
+        scale: Vec3::ONE,
+        ..stamp_transform.compute_transform()
+    });
+    let stamped =
+        stamp_transform.mul_transform(Transform::from_translation(STAMPED_OFFSET.extend(0.0)));
+    Transform::from_matrix(dossier_transform.compute_matrix().inverse() * stamped.compute_matrix())
+}
+
+/// The part of a mark that lands inside an area of the paper, relative to the mark. Since the
+/// mark is tilted, this is the biggest part of it, square with the mark, that is wholly inside
+/// the area
+fn stamped_within(stamped_transform: &Transform, area: Rect) -> Rect {
+    let center = stamped_transform.translation.truncate() - area.center();
+    let half = area.half_size();
+    let (sin, cos) = stamped_transform
+        .rotation
+        .to_euler(EulerRot::XYZ)
+        .2
+        .sin_cos();
+    let mut part = Rect::from_center_size(Vec2::ZERO, STAMPED_SIZE);
+    // The tilt moves each edge of the mark further out at one end than the other, so each is
+    // pulled in until its furthest out end is inside
+    let (low, high) = (
+        (sin * part.min.y).min(sin * part.max.y),
+        (sin * part.min.y).max(sin * part.max.y),
+    );
+    part.max.x = part.max.x.min((half.x - center.x + low) / cos);
+    part.min.x = part.min.x.max((-half.x - center.x + high) / cos);
+    let (low, high) = (
+        (sin * part.min.x).min(sin * part.max.x),
+        (sin * part.min.x).max(sin * part.max.x),
     );
-    let dossier = Rect::from_center_size(dossier_transform.translation.truncate(), dossier_size);
-    fits(dossier, stamped)
+    part.max.y = part.max.y.min((half.y - center.y - high) / cos);
+    part.min.y = part.min.y.max((-half.y - center.y - low) / cos);
+    part
 }
 
-fn fits(outer: Rect, inner: Rect) -> bool {
-    outer.min.cmple(inner.min).all() && inner.max.cmple(outer.max).all()
+/// How much of a whole mark a part of it is
+fn coverage(part: Rect) -> f32 {
+    if part.is_empty() {
+        return 0.0;
+    }
+    part.width() * part.height() / (STAMPED_SIZE.x * STAMPED_SIZE.y)
 }
 
 fn drag(mouse: Res<Mouse>, mut query: Query<&mut Transform, With<BeingDragged>>) {
@@ -584,16 +1075,69 @@ fn drag(mouse: Res<Mouse>, mut query: Query<&mut Transform, With<BeingDragged>>)
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum GameState {
+pub enum GameState {
+    Loading,
+    Title,
+    Intro,
     Desk,
     Newspaper,
+    Fired,
+    /// Looking back over the campaign once it's over
+    Review,
 }
 
-fn check_timer(mut stamp_status: ResMut<StampStatus>, time: Res<Time>, mut commands: Commands) {
-    if let StampStatus::PickedUp(timer) = &mut *stamp_status {
-        timer.tick(time.delta());
-        if timer.just_finished() {
-            commands.insert_resource(NextState(GameState::Newspaper))
+/// The analyst's standing with the agency, kept up to date as dossiers are filed
+#[derive(SystemParam)]
+struct Records<'w, 's> {
+    campaign: ResMut<'w, Campaign>,
+    log: ResMut<'w, DecisionLog>,
+    handbook: Res<'w, Handbook>,
+    #[system_param(ignore)]
+    marker: PhantomData<&'s ()>,
+}
+
+fn file_dossier(
+    mut filed_events: EventReader<DossierFiled>,
+    mut records: Records,
+    dossier: Query<&Dossier>,
+    assets: DeskAssets,
+    mut transitions: EventWriter<RequestTransition>,
+    mut commands: Commands,
+) {
+    for &DossierFiled(entity) in filed_events.iter() {
+        let filed = dossier.get(entity).unwrap();
+        let recommendation = filed
+            .decision
+            .recommendation()
+            .expect("only stamped dossiers are filed");
+        let complies = records.handbook.complies(
+            assets.dossiers.get(&filed.asset).unwrap(),
+            recommendation.stamp,
+            records.campaign.today_index(),
+        );
+        records
+            .log
+            .record(filed.asset.clone(), recommendation.stamp, complies);
+        if !complies {
+            // Gone before the letter arrives, so there's no quitting to keep the job
+            delete_save();
+        }
+        match records.campaign.finish_dossier(recommendation, complies) {
+            // Going against the handbook ends your career
+            _ if !complies => transitions.send(RequestTransition(GameState::Fired)),
+            // The next dossier arrives in the inbox
+            Some(next) => {
+                commands.entity(entity).despawn_recursive();
+                spawn_dossier(
+                    &mut commands,
+                    &assets.asset_server,
+                    next.clone(),
+                    assets.dossiers.get(next).unwrap(),
+                    DecisionState::Undecided,
+                );
+            }
+            // Day ends
+            None => transitions.send(RequestTransition(GameState::Newspaper)),
         }
     }
 }
@@ -613,7 +1157,7 @@ pub struct Mouse {
 
 /// References
 /// 1. calc_mouse_pos
-/// https://bevy-cheatbook.github.io/cookbook/cursor2world.html
+///    https://bevy-cheatbook.github.io/cookbook/cursor2world.html
 ///
 /// Runs on a separate stage before everything else.
 fn calc_mouse_pos(
@@ -647,3 +1191,53 @@ fn calc_mouse_pos(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AREA: Rect = Rect {
+        min: Vec2::new(-200.0, -200.0),
+        max: Vec2::new(200.0, 200.0),
+    };
+
+    #[test]
+    fn stamped_within_keeps_a_mark_wholly_inside() {
+        let stamped = Transform::from_xyz(10.0, -20.0, 0.0);
+        let part = stamped_within(&stamped, AREA);
+        assert_eq!(part, Rect::from_center_size(Vec2::ZERO, STAMPED_SIZE));
+        assert_eq!(coverage(part), 1.0);
+    }
+
+    #[test]
+    fn stamped_within_cuts_a_mark_at_the_edge() {
+        let stamped = Transform::from_xyz(200.0, 0.0, 0.0);
+        let part = stamped_within(&stamped, AREA);
+        assert_eq!(part.min, -STAMPED_SIZE / 2.0);
+        assert_eq!(part.max, Vec2::new(0.0, STAMPED_SIZE.y / 2.0));
+        assert_eq!(coverage(part), 0.5);
+    }
+
+    #[test]
+    fn stamped_within_is_empty_for_a_mark_outside() {
+        let stamped = Transform::from_xyz(400.0, 0.0, 0.0);
+        assert_eq!(coverage(stamped_within(&stamped, AREA)), 0.0);
+    }
+
+    #[test]
+    fn stamped_within_keeps_a_tilted_mark_inside() {
+        let stamped =
+            Transform::from_xyz(180.0, 160.0, 0.0).with_rotation(Quat::from_rotation_z(0.2));
+        let part = stamped_within(&stamped, AREA);
+        assert!(!part.is_empty());
+        for corner in [
+            part.min,
+            part.max,
+            Vec2::new(part.min.x, part.max.y),
+            Vec2::new(part.max.x, part.min.y),
+        ] {
+            let on_paper = stamped.transform_point(corner.extend(0.0)).truncate();
+            assert!(AREA.contains(on_paper), "{on_paper} is off the paper");
+        }
+    }
+}
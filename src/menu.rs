@@ -0,0 +1,372 @@
+//! The title screen and the intro shown before the first day at the desk, and the sound
+//! settings reachable from both the title and the desk.
+use bevy::prelude::*;
+
+use crate::{
+    audio::SoundCategory,
+    campaign::{Campaign, CampaignAsset},
+    review::DecisionLog,
+    save::{has_save, ContinueGame},
+    settings::Settings,
+    transition::RequestTransition,
+    CampaignHandle, GameState, OnDesk,
+};
+
+#[derive(Component)]
+pub struct OnTitle;
+
+#[derive(Component)]
+pub struct OnIntro;
+
+#[derive(Component, Clone, Copy)]
+pub enum MenuButton {
+    StartGame,
+    /// Only on the title if there's a saved game
+    Continue,
+    GoToDesk,
+    /// Back from looking over the campaign
+    BackToTitle,
+    /// On to looking over the campaign, from the letter of termination
+    GoToReview,
+    /// Steps through the volumes of a category of sound
+    Volume(SoundCategory),
+    /// Shows or hides the volume buttons on the desk
+    SoundSettings,
+}
+
+/// The volume buttons on the desk, hidden until asked for
+#[derive(Component)]
+pub struct SoundPanel;
+
+/// How much each click on a volume button turns it down, wrapping back round to full
+const VOLUME_STEP: f32 = 0.25;
+
+pub const BUTTON_COLOR: Color = Color::rgb(0.15, 0.15, 0.15);
+pub const BUTTON_HOVERED_COLOR: Color = Color::rgb(0.25, 0.25, 0.25);
+
+pub fn spawn_title(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    settings: Res<Settings>,
+) {
+    let screen = spawn_screen(
+        &mut commands,
+        &asset_server,
+        OnTitle,
+        "Welcome to the CIA",
+        "Start Game",
+        MenuButton::StartGame,
+    );
+
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    commands.entity(screen).with_children(|parent| {
+        if has_save() {
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: Style {
+                            size: Size::new(Val::Px(250.0), Val::Px(65.0)),
+                            margin: UiRect::top(Val::Px(20.0)),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        background_color: BUTTON_COLOR.into(),
+                        ..default()
+                    },
+                    MenuButton::Continue,
+                ))
+                .with_children(|button| {
+                    button.spawn(TextBundle::from_section(
+                        "Continue",
+                        TextStyle {
+                            font: font.clone(),
+                            font_size: 32.0,
+                            color: Color::WHITE,
+                        },
+                    ));
+                });
+        }
+
+        parent
+            .spawn(NodeBundle {
+                style: Style {
+                    margin: UiRect::top(Val::Px(60.0)),
+                    ..default()
+                },
+                ..default()
+            })
+            .with_children(|row| spawn_volume_buttons(row, &font, &settings));
+    });
+}
+
+/// A button for each category of sound, showing its volume
+fn spawn_volume_buttons(parent: &mut ChildBuilder, font: &Handle<Font>, settings: &Settings) {
+    for category in SoundCategory::ALL {
+        parent
+            .spawn((
+                ButtonBundle {
+                    style: Style {
+                        size: Size::new(Val::Px(200.0), Val::Px(40.0)),
+                        margin: UiRect::all(Val::Px(5.0)),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    background_color: BUTTON_COLOR.into(),
+                    ..default()
+                },
+                MenuButton::Volume(category),
+            ))
+            .with_children(|button| {
+                button.spawn(TextBundle::from_section(
+                    volume_label(category, settings),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 20.0,
+                        color: Color::WHITE,
+                    },
+                ));
+            });
+    }
+}
+
+/// A button in the top right of the desk that opens the volume buttons under it, so the sound
+/// can be changed without leaving work
+pub fn spawn_sound_settings(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    settings: Res<Settings>,
+) {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        right: Val::Px(15.0),
+                        top: Val::Px(15.0),
+                        ..default()
+                    },
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::FlexEnd,
+                    ..default()
+                },
+                ..default()
+            },
+            OnDesk,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: Style {
+                            size: Size::new(Val::Px(100.0), Val::Px(40.0)),
+                            margin: UiRect::all(Val::Px(5.0)),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        background_color: BUTTON_COLOR.into(),
+                        ..default()
+                    },
+                    MenuButton::SoundSettings,
+                ))
+                .with_children(|button| {
+                    button.spawn(TextBundle::from_section(
+                        "Sound",
+                        TextStyle {
+                            font: font.clone(),
+                            font_size: 20.0,
+                            color: Color::WHITE,
+                        },
+                    ));
+                });
+            parent
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            flex_direction: FlexDirection::Column,
+                            ..default()
+                        },
+                        visibility: Visibility { is_visible: false },
+                        ..default()
+                    },
+                    SoundPanel,
+                ))
+                .with_children(|panel| spawn_volume_buttons(panel, &font, &settings));
+        });
+}
+
+/// Opens or closes the volume buttons on the desk
+pub fn toggle_sound_panel(
+    buttons: Query<(&Interaction, &MenuButton), Changed<Interaction>>,
+    mut panel: Query<&mut Visibility, With<SoundPanel>>,
+) {
+    for (interaction, button) in &buttons {
+        if let (Interaction::Clicked, MenuButton::SoundSettings) = (interaction, button) {
+            for mut visibility in &mut panel {
+                visibility.is_visible = !visibility.is_visible;
+            }
+        }
+    }
+}
+
+fn volume_label(category: SoundCategory, settings: &Settings) -> String {
+    format!(
+        "{}: {:.0}%",
+        category.label(),
+        category.volume(settings) * 100.0
+    )
+}
+
+pub fn spawn_intro(mut commands: Commands, asset_server: Res<AssetServer>) {
+    spawn_screen(
+        &mut commands,
+        &asset_server,
+        OnIntro,
+        "Welcome to your first day as an analyst at the CIA!",
+        "Continue",
+        MenuButton::GoToDesk,
+    );
+}
+
+/// A black screen with a line of text and a button under it
+fn spawn_screen(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    marker: impl Component,
+    text: &str,
+    button_text: &str,
+    button: MenuButton,
+) -> Entity {
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: Color::BLACK.into(),
+                ..default()
+            },
+            marker,
+        ))
+        .with_children(|parent| {
+            parent.spawn(
+                TextBundle::from_section(
+                    text,
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 48.0,
+                        color: Color::WHITE,
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::all(Val::Px(30.0)),
+                    ..default()
+                }),
+            );
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: Style {
+                            size: Size::new(Val::Px(250.0), Val::Px(65.0)),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        background_color: BUTTON_COLOR.into(),
+                        ..default()
+                    },
+                    button,
+                ))
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        button_text,
+                        TextStyle {
+                            font,
+                            font_size: 32.0,
+                            color: Color::WHITE,
+                        },
+                    ));
+                });
+        })
+        .id()
+}
+
+pub fn press_menu_buttons(
+    mut buttons: Query<
+        (&Interaction, &MenuButton, &mut BackgroundColor),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut settings: ResMut<Settings>,
+    mut transitions: EventWriter<RequestTransition>,
+    mut continues: EventWriter<ContinueGame>,
+    campaign_handle: Res<CampaignHandle>,
+    campaigns: Res<Assets<CampaignAsset>>,
+    mut commands: Commands,
+) {
+    for (interaction, button, mut color) in &mut buttons {
+        match interaction {
+            Interaction::Clicked => match *button {
+                MenuButton::StartGame => {
+                    // From the first day, even if there's a game to continue or one just ended
+                    let campaign = campaigns.get(&campaign_handle.0).unwrap();
+                    commands.insert_resource(Campaign::new(campaign));
+                    commands.insert_resource(DecisionLog::default());
+                    transitions.send(RequestTransition(GameState::Intro));
+                }
+                MenuButton::Continue => continues.send(ContinueGame),
+                MenuButton::GoToDesk => transitions.send(RequestTransition(GameState::Desk)),
+                MenuButton::BackToTitle => transitions.send(RequestTransition(GameState::Title)),
+                MenuButton::GoToReview => transitions.send(RequestTransition(GameState::Review)),
+                MenuButton::Volume(category) => {
+                    let volume = category.volume_mut(&mut settings);
+                    *volume = if *volume <= 0.0 {
+                        1.0
+                    } else {
+                        (*volume - VOLUME_STEP).max(0.0)
+                    };
+                }
+                // Opened and closed by toggle_sound_panel
+                MenuButton::SoundSettings => {}
+            },
+            Interaction::Hovered => *color = BUTTON_HOVERED_COLOR.into(),
+            Interaction::None => *color = BUTTON_COLOR.into(),
+        }
+    }
+}
+
+pub fn update_volume_labels(
+    settings: Res<Settings>,
+    buttons: Query<(&MenuButton, &Children)>,
+    mut text: Query<&mut Text>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+    for (button, children) in &buttons {
+        if let MenuButton::Volume(category) = *button {
+            text.get_mut(children[0]).unwrap().sections[0].value =
+                volume_label(category, &settings);
+        }
+    }
+}
+
+pub fn despawn_title(query: Query<Entity, With<OnTitle>>, mut commands: Commands) {
+    for entity in &query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+pub fn despawn_intro(query: Query<Entity, With<OnIntro>>, mut commands: Commands) {
+    for entity in &query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,334 @@
+//! The newspaper delivered at the end of each day, with a front page for every decision the
+//! analyst filed that day.
+//!
+//! Each front page spins in headline first, then lands on the desk to be picked up and read
+//! like any other document. Once they've all landed, "Continue" moves on to the next day.
+use std::f32::consts::TAU;
+
+use bevy::{prelude::*, text::Text2dBounds};
+
+use crate::{
+    campaign::Campaign,
+    dossier::{DossierAsset, Outcome},
+    menu::{BUTTON_COLOR, BUTTON_HOVERED_COLOR},
+    transition::RequestTransition,
+    DragHitBox, DragKind, GameState,
+};
+
+const PAPER_SIZE: Vec2 = Vec2::new(520.0, 680.0);
+/// How big a front page is once it's landed on the desk
+const SETTLED_SCALE: f32 = 0.8;
+/// How long a front page takes to spin in
+const REVEAL_SECONDS: f32 = 1.2;
+/// How long after one front page starts spinning in the next one does
+const REVEAL_STAGGER_SECONDS: f32 = 0.9;
+/// How many times a front page spins on its way in
+const REVEAL_TURNS: f32 = 3.0;
+/// Above everything on the desk while spinning in
+const REVEAL_Z: f32 = 900.0;
+
+#[derive(Component)]
+pub struct OnNewspaper;
+
+/// The paper of a front page, with its text and photo as children
+#[derive(Component)]
+pub struct FrontPage;
+
+/// A front page on its way in, and where it lands
+#[derive(Component)]
+pub struct Reveal {
+    delay: Timer,
+    spin: Timer,
+    landing: Transform,
+}
+
+#[derive(Component)]
+pub struct ContinueButton;
+
+pub fn spawn_newspaper(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    campaign: Res<Campaign>,
+    dossiers: Res<Assets<DossierAsset>>,
+    images: Res<Assets<Image>>,
+) {
+    if let Some(today) = campaign.today() {
+        let pages = today.decisions.len();
+        for (i, decision) in today.decisions.iter().enumerate() {
+            let dossier = dossiers.get(&decision.dossier).unwrap();
+            // The first decision of the day comes in last, so it ends up on top of the pile
+            let order = pages - 1 - i;
+            let landing = Transform::from_xyz(-30.0 * i as f32, 20.0 * i as f32, 0.0)
+                .with_rotation(Quat::from_rotation_z(if i % 2 == 0 { -0.04 } else { 0.05 }))
+                .with_scale(Vec3::splat(SETTLED_SCALE));
+            let page = spawn_front_page(
+                &mut commands,
+                &asset_server,
+                &images,
+                &dossier.outcomes[&decision.stamp],
+            );
+            commands.entity(page).insert(Reveal {
+                delay: Timer::from_seconds(REVEAL_STAGGER_SECONDS * order as f32, TimerMode::Once),
+                spin: Timer::from_seconds(REVEAL_SECONDS, TimerMode::Once),
+                landing,
+            });
+        }
+    }
+
+    spawn_continue_button(&mut commands, &asset_server);
+}
+
+fn spawn_front_page(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    images: &Assets<Image>,
+    outcome: &Outcome,
+) -> Entity {
+    let paper_size = PAPER_SIZE;
+    let margin = 16.0;
+    let masthead_size = Vec2::new(60.0, 60.0);
+    let headline_height = 80.0;
+    let subheadline_height = 50.0;
+    let photo_box = Vec2::new(340.0, 240.0);
+
+    let text_width = paper_size.x - margin * 2.0;
+    let left = -paper_size.x / 2.0 + margin;
+    let top = paper_size.y / 2.0 - margin;
+
+    let headline_font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    let mono_font = asset_server.load("fonts/FiraMono-Medium.ttf");
+
+    commands
+        .spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: Color::rgb(0.95, 0.93, 0.87),
+                    custom_size: Some(paper_size),
+                    ..default()
+                },
+                // Nowhere to be seen until it starts spinning in
+                transform: Transform::from_xyz(0.0, 0.0, REVEAL_Z).with_scale(Vec3::ZERO),
+                ..default()
+            },
+            FrontPage,
+            OnNewspaper,
+        ))
+        .with_children(|page| {
+            page.spawn(SpriteBundle {
+                texture: asset_server.load("random-newspaper.png"),
+                sprite: Sprite {
+                    custom_size: Some(masthead_size),
+                    ..default()
+                },
+                transform: Transform::from_xyz(
+                    left + masthead_size.x / 2.0,
+                    top - masthead_size.y / 2.0,
+                    1.0,
+                ),
+                ..default()
+            });
+            page.spawn(Text2dBundle {
+                text: Text::from_section(
+                    "The World Tribune",
+                    TextStyle {
+                        font: headline_font.clone(),
+                        font_size: 44.0,
+                        color: Color::BLACK,
+                    },
+                )
+                .with_alignment(TextAlignment::CENTER_LEFT),
+                transform: Transform::from_xyz(
+                    left + masthead_size.x + margin,
+                    top - masthead_size.y / 2.0,
+                    1.0,
+                ),
+                ..default()
+            });
+
+            let mut y = top - masthead_size.y - margin;
+            page.spawn(Text2dBundle {
+                text: Text::from_section(
+                    outcome.headline.clone(),
+                    TextStyle {
+                        font: headline_font,
+                        font_size: 30.0,
+                        color: Color::BLACK,
+                    },
+                ),
+                text_2d_bounds: Text2dBounds {
+                    size: Vec2::new(text_width, headline_height),
+                },
+                transform: Transform::from_xyz(left, y, 1.0),
+                ..default()
+            });
+            y -= headline_height;
+
+            page.spawn(Text2dBundle {
+                text: Text::from_section(
+                    outcome.subheadline.clone(),
+                    TextStyle {
+                        font: mono_font.clone(),
+                        font_size: 16.0,
+                        color: Color::DARK_GRAY,
+                    },
+                ),
+                text_2d_bounds: Text2dBounds {
+                    size: Vec2::new(text_width, subheadline_height),
+                },
+                transform: Transform::from_xyz(left, y, 1.0),
+                ..default()
+            });
+            y -= subheadline_height;
+
+            if let Some(photo) = &outcome.photo {
+                let texture = asset_server.load(photo.as_str());
+                // As big as fits in the box without stretching it
+                let photo_size = match images.get(&texture) {
+                    Some(image) => {
+                        let size = image.size();
+                        size * (photo_box / size).min_element()
+                    }
+                    None => photo_box,
+                };
+                page.spawn(SpriteBundle {
+                    texture,
+                    sprite: Sprite {
+                        custom_size: Some(photo_size),
+                        ..default()
+                    },
+                    transform: Transform::from_xyz(0.0, y - photo_size.y / 2.0, 1.0),
+                    ..default()
+                });
+                y -= photo_size.y + margin;
+            }
+
+            page.spawn(Text2dBundle {
+                text: Text::from_section(
+                    outcome.body.clone(),
+                    TextStyle {
+                        font: mono_font,
+                        font_size: 14.0,
+                        color: Color::BLACK,
+                    },
+                ),
+                text_2d_bounds: Text2dBounds {
+                    size: Vec2::new(text_width, y + paper_size.y / 2.0 - margin),
+                },
+                transform: Transform::from_xyz(left, y, 1.0),
+                ..default()
+            });
+        })
+        .id()
+}
+
+fn spawn_continue_button(commands: &mut Commands, asset_server: &AssetServer) {
+    commands
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    size: Size::new(Val::Px(220.0), Val::Px(50.0)),
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        right: Val::Px(20.0),
+                        bottom: Val::Px(20.0),
+                        ..default()
+                    },
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: BUTTON_COLOR.into(),
+                visibility: Visibility { is_visible: false },
+                ..default()
+            },
+            ContinueButton,
+            OnNewspaper,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Continue",
+                TextStyle {
+                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                    font_size: 28.0,
+                    color: Color::WHITE,
+                },
+            ));
+        });
+}
+
+/// Spins and zooms front pages in one after another. Once a page lands it can be picked up
+pub fn reveal_front_pages(
+    time: Res<Time>,
+    mut pages: Query<(Entity, &mut Reveal, &mut Transform)>,
+    mut commands: Commands,
+) {
+    for (entity, mut reveal, mut transform) in &mut pages {
+        if !reveal.delay.tick(time.delta()).finished() {
+            continue;
+        }
+        reveal.spin.tick(time.delta());
+        if reveal.spin.finished() {
+            *transform = reveal.landing;
+            commands
+                .entity(entity)
+                .remove::<Reveal>()
+                .insert(DragHitBox {
+                    size: PAPER_SIZE,
+                    kind: DragKind::Newspaper,
+                    alpha_mask: false,
+                });
+            continue;
+        }
+
+        // Fast at first, slowing down as it lands
+        let progress = 1.0 - (1.0 - reveal.spin.percent()).powi(3);
+        let spin = (1.0 - progress) * REVEAL_TURNS * TAU;
+        transform.translation = (reveal.landing.translation.truncate() * progress).extend(REVEAL_Z);
+        transform.rotation = reveal.landing.rotation * Quat::from_rotation_z(spin);
+        transform.scale = reveal.landing.scale * progress;
+    }
+}
+
+/// Shows "Continue" once every front page has landed
+pub fn show_continue_button(
+    reveals: Query<(), With<Reveal>>,
+    mut button: Query<&mut Visibility, With<ContinueButton>>,
+) {
+    let landed = reveals.is_empty();
+    for mut visibility in &mut button {
+        if visibility.is_visible != landed {
+            visibility.is_visible = landed;
+        }
+    }
+}
+
+/// "Continue" starts the next day, or looks back over the campaign once it's over
+pub fn press_continue(
+    mut buttons: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<ContinueButton>),
+    >,
+    mut campaign: ResMut<Campaign>,
+    mut transitions: EventWriter<RequestTransition>,
+) {
+    for (interaction, mut color) in &mut buttons {
+        match interaction {
+            Interaction::Clicked => {
+                let next = if campaign.advance_day() {
+                    GameState::Desk
+                } else {
+                    GameState::Review
+                };
+                transitions.send(RequestTransition(next));
+            }
+            Interaction::Hovered => *color = BUTTON_HOVERED_COLOR.into(),
+            Interaction::None => *color = BUTTON_COLOR.into(),
+        }
+    }
+}
+
+pub fn despawn_newspaper(query: Query<Entity, With<OnNewspaper>>, mut commands: Commands) {
+    for entity in &query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,257 @@
+//! A log of every dossier the analyst filed, and the screen looking back over it once the
+//! campaign is over or the analyst is fired.
+use std::time::Duration;
+
+use bevy::{prelude::*, time::Stopwatch};
+
+use crate::{
+    campaign::Campaign,
+    dossier::{DossierAsset, Outcome},
+    menu::{MenuButton, BUTTON_COLOR},
+    StampKind,
+};
+
+/// Every dossier filed so far, in order
+#[derive(Resource, Default)]
+pub struct DecisionLog {
+    pub entries: Vec<LoggedDecision>,
+    /// Time at the desk since the last dossier was filed
+    since_last: Stopwatch,
+}
+
+pub struct LoggedDecision {
+    pub dossier: Handle<DossierAsset>,
+    pub stamp: StampKind,
+    /// How long the analyst spent on the dossier
+    pub time_taken: Duration,
+    /// Whether the recommendation went by the handbook
+    pub complied: bool,
+}
+
+impl LoggedDecision {
+    /// The front page the newspaper ran for the decision
+    pub fn outcome<'a>(&self, dossiers: &'a Assets<DossierAsset>) -> &'a Outcome {
+        &dossiers.get(&self.dossier).unwrap().outcomes[&self.stamp]
+    }
+}
+
+impl DecisionLog {
+    pub fn new(entries: Vec<LoggedDecision>) -> Self {
+        DecisionLog {
+            entries,
+            since_last: default(),
+        }
+    }
+
+    /// Logs the dossier the analyst just filed, starting the clock on the next one
+    pub fn record(&mut self, dossier: Handle<DossierAsset>, stamp: StampKind, complied: bool) {
+        self.entries.push(LoggedDecision {
+            dossier,
+            stamp,
+            time_taken: self.since_last.elapsed(),
+            complied,
+        });
+        self.since_last.reset();
+    }
+}
+
+/// Only counts time spent at the desk, not reading the paper
+pub fn time_decisions(time: Res<Time>, mut log: ResMut<DecisionLog>) {
+    log.since_last.tick(time.delta());
+}
+
+#[derive(Component)]
+pub struct OnReview;
+
+const HEADSHOT_SIZE: Vec2 = Vec2::new(80.0, 80.0);
+const FRONT_PAGE_PHOTO_SIZE: Vec2 = Vec2::new(120.0, 80.0);
+
+pub fn spawn_review(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    log: Res<DecisionLog>,
+    campaign: Res<Campaign>,
+    dossiers: Res<Assets<DossierAsset>>,
+) {
+    let bold_font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    let mono_font = asset_server.load("fonts/FiraMono-Medium.ttf");
+    let text_style = |font: &Handle<Font>, font_size, color| TextStyle {
+        font: font.clone(),
+        font_size,
+        color,
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: Color::BLACK.into(),
+                ..default()
+            },
+            OnReview,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Performance Review",
+                text_style(&bold_font, 48.0, Color::WHITE),
+            ));
+            parent.spawn(
+                TextBundle::from_section(
+                    format!(
+                        "{} of {} recommendations filed by the handbook{}",
+                        campaign.commendations(),
+                        log.entries.len(),
+                        if campaign.dismissed() {
+                            "\nEmployment terminated"
+                        } else {
+                            ""
+                        },
+                    ),
+                    text_style(&mono_font, 18.0, Color::GRAY),
+                )
+                .with_style(Style {
+                    margin: UiRect::bottom(Val::Px(30.0)),
+                    ..default()
+                }),
+            );
+
+            for entry in &log.entries {
+                let dossier = dossiers.get(&entry.dossier).unwrap();
+                let outcome = entry.outcome(&dossiers);
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            align_items: AlignItems::Center,
+                            margin: UiRect::vertical(Val::Px(10.0)),
+                            ..default()
+                        },
+                        ..default()
+                    })
+                    .with_children(|row| {
+                        row.spawn(ImageBundle {
+                            image: UiImage(asset_server.load(dossier.photo.as_str())),
+                            style: Style {
+                                size: Size::new(Val::Px(HEADSHOT_SIZE.x), Val::Px(HEADSHOT_SIZE.y)),
+                                margin: UiRect::right(Val::Px(16.0)),
+                                ..default()
+                            },
+                            ..default()
+                        });
+                        row.spawn(NodeBundle {
+                            style: Style {
+                                flex_direction: FlexDirection::Column,
+                                size: Size::new(Val::Px(320.0), Val::Auto),
+                                ..default()
+                            },
+                            ..default()
+                        })
+                        .with_children(|column| {
+                            column.spawn(TextBundle::from_section(
+                                format!("{}, {}", dossier.name, dossier.title),
+                                text_style(&bold_font, 22.0, Color::WHITE),
+                            ));
+                            column.spawn(TextBundle::from_section(
+                                format!(
+                                    "Stamped {} after {}",
+                                    entry.stamp.label(),
+                                    format_duration(entry.time_taken),
+                                ),
+                                text_style(&mono_font, 16.0, Color::WHITE),
+                            ));
+                            column.spawn(TextBundle::from_section(
+                                if entry.complied {
+                                    "By the handbook"
+                                } else {
+                                    "Against the handbook"
+                                },
+                                text_style(
+                                    &mono_font,
+                                    16.0,
+                                    if entry.complied {
+                                        Color::GREEN
+                                    } else {
+                                        Color::RED
+                                    },
+                                ),
+                            ));
+                        });
+
+                        if let Some(photo) = &outcome.photo {
+                            row.spawn(ImageBundle {
+                                image: UiImage(asset_server.load(photo.as_str())),
+                                style: Style {
+                                    size: Size::new(
+                                        Val::Px(FRONT_PAGE_PHOTO_SIZE.x),
+                                        Val::Px(FRONT_PAGE_PHOTO_SIZE.y),
+                                    ),
+                                    margin: UiRect::horizontal(Val::Px(16.0)),
+                                    ..default()
+                                },
+                                ..default()
+                            });
+                        }
+                        row.spawn(NodeBundle {
+                            style: Style {
+                                flex_direction: FlexDirection::Column,
+                                max_size: Size::new(Val::Px(420.0), Val::Undefined),
+                                ..default()
+                            },
+                            ..default()
+                        })
+                        .with_children(|column| {
+                            column.spawn(TextBundle::from_section(
+                                outcome.headline.clone(),
+                                text_style(&bold_font, 20.0, Color::WHITE),
+                            ));
+                            column.spawn(TextBundle::from_section(
+                                outcome.subheadline.clone(),
+                                text_style(&mono_font, 14.0, Color::GRAY),
+                            ));
+                        });
+                    });
+            }
+
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: Style {
+                            size: Size::new(Val::Px(250.0), Val::Px(65.0)),
+                            margin: UiRect::top(Val::Px(30.0)),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        background_color: BUTTON_COLOR.into(),
+                        ..default()
+                    },
+                    MenuButton::BackToTitle,
+                ))
+                .with_children(|button| {
+                    button.spawn(TextBundle::from_section(
+                        "Back to Title",
+                        text_style(&bold_font, 32.0, Color::WHITE),
+                    ));
+                });
+        });
+}
+
+/// Like "1m 05s", or just "42s" under a minute
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    match seconds / 60 {
+        0 => format!("{seconds}s"),
+        minutes => format!("{minutes}m {:02}s", seconds % 60),
+    }
+}
+
+pub fn despawn_review(query: Query<Entity, With<OnReview>>, mut commands: Commands) {
+    for entity in &query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,205 @@
+//! Saving progress through the campaign after every change, and picking it back up with
+//! "Continue" on the title screen.
+use std::time::Duration;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    campaign::{Campaign, CampaignAsset, Decision},
+    decision::Recommendation,
+    review::{DecisionLog, LoggedDecision},
+    storage,
+    transition::RequestTransition,
+    CampaignHandle, GameState, StampKind,
+};
+
+const SAVE_KEY: &str = "save";
+
+#[derive(Serialize, Deserialize)]
+struct SaveFile {
+    today: usize,
+    commendations: u32,
+    /// What was decided each day so far
+    days: Vec<Vec<SavedDecision>>,
+    /// Every dossier filed so far, for the review at the end
+    #[serde(default)]
+    log: Vec<SavedLogEntry>,
+    /// What's stamped on the dossier being worked on, if anything
+    #[serde(default)]
+    stamped: Option<SavedStamp>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SavedStamp {
+    stamp: StampKind,
+    position: (f32, f32),
+    #[serde(default)]
+    rotation: f32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SavedDecision {
+    /// Path of the dossier file, relative to `assets/`
+    dossier: String,
+    stamp: StampKind,
+    position: (f32, f32),
+}
+
+#[derive(Serialize, Deserialize)]
+struct SavedLogEntry {
+    dossier: String,
+    stamp: StampKind,
+    seconds: f32,
+    complied: bool,
+}
+
+fn read_save() -> Option<SaveFile> {
+    let saved = storage::read(SAVE_KEY)?;
+    ron::from_str(&saved)
+        .map_err(|err| warn!("ignoring unreadable save: {err}"))
+        .ok()
+}
+
+/// Whether there's a game to continue
+pub fn has_save() -> bool {
+    read_save().is_some()
+}
+
+/// Saves whenever the campaign moves on, except when it's just been started or loaded
+pub fn save_game(
+    campaign: Option<Res<Campaign>>,
+    log: Res<DecisionLog>,
+    asset_server: Res<AssetServer>,
+) {
+    let Some(campaign) = campaign else {
+        return;
+    };
+    if !campaign.is_changed() || campaign.is_added() {
+        return;
+    }
+    // A fired analyst's save is deleted as soon as they're found out
+    if campaign.dismissed() {
+        return;
+    }
+    // Nothing left to continue once the campaign is over
+    if campaign.today().is_none() {
+        storage::remove(SAVE_KEY);
+        return;
+    }
+
+    let dossier_path = |dossier| {
+        asset_server
+            .get_handle_path(dossier)
+            .expect("dossiers are loaded from files")
+            .path()
+            .to_string_lossy()
+            .into_owned()
+    };
+    let save = SaveFile {
+        today: campaign.today_index(),
+        commendations: campaign.commendations(),
+        days: campaign
+            .days()
+            .iter()
+            .map(|day| {
+                day.decisions
+                    .iter()
+                    .map(|decision| SavedDecision {
+                        dossier: dossier_path(&decision.dossier),
+                        stamp: decision.stamp,
+                        position: decision.position.into(),
+                    })
+                    .collect()
+            })
+            .collect(),
+        log: log
+            .entries
+            .iter()
+            .map(|entry| SavedLogEntry {
+                dossier: dossier_path(&entry.dossier),
+                stamp: entry.stamp,
+                seconds: entry.time_taken.as_secs_f32(),
+                complied: entry.complied,
+            })
+            .collect(),
+        stamped: campaign.stamped().map(|recommendation| SavedStamp {
+            stamp: recommendation.stamp,
+            position: recommendation.position.into(),
+            rotation: recommendation.rotation,
+        }),
+    };
+    storage::write(
+        SAVE_KEY,
+        &ron::ser::to_string_pretty(&save, default()).unwrap(),
+    );
+}
+
+/// A fired analyst doesn't get to continue
+pub fn delete_save() {
+    storage::remove(SAVE_KEY);
+}
+
+/// Sent to pick up the saved game
+pub struct ContinueGame;
+
+pub fn continue_game(
+    mut continues: EventReader<ContinueGame>,
+    campaign_handle: Res<CampaignHandle>,
+    campaigns: Res<Assets<CampaignAsset>>,
+    asset_server: Res<AssetServer>,
+    mut transitions: EventWriter<RequestTransition>,
+    mut commands: Commands,
+) {
+    if continues.iter().count() == 0 {
+        return;
+    }
+    let Some(save) = read_save() else {
+        return;
+    };
+
+    let decisions = save
+        .days
+        .into_iter()
+        .map(|day| {
+            day.into_iter()
+                .map(|decision| Decision {
+                    dossier: asset_server.get_handle(decision.dossier.as_str()),
+                    stamp: decision.stamp,
+                    position: decision.position.into(),
+                })
+                .collect()
+        })
+        .collect();
+    let log = save
+        .log
+        .into_iter()
+        .map(|entry| LoggedDecision {
+            dossier: asset_server.get_handle(entry.dossier.as_str()),
+            stamp: entry.stamp,
+            time_taken: Duration::from_secs_f32(entry.seconds),
+            complied: entry.complied,
+        })
+        .collect();
+    let asset = campaigns.get(&campaign_handle.0).unwrap();
+    let stamped = save.stamped.map(|stamped| Recommendation {
+        stamp: stamped.stamp,
+        position: stamped.position.into(),
+        rotation: stamped.rotation,
+    });
+    let Some(campaign) =
+        Campaign::restore(asset, save.today, save.commendations, decisions, stamped)
+    else {
+        warn!("ignoring save that doesn't fit the campaign");
+        return;
+    };
+
+    // Pick up where the analyst left off, reading the paper if the day's work was done
+    let state = match campaign.current_dossier() {
+        Some(_) => GameState::Desk,
+        None => GameState::Newspaper,
+    };
+    commands.insert_resource(campaign);
+    commands.insert_resource(DecisionLog::new(log));
+    transitions.send(RequestTransition(state));
+}
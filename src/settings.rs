@@ -0,0 +1,52 @@
+//! Player settings, kept between runs.
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::storage;
+
+const SETTINGS_KEY: &str = "settings";
+
+#[derive(Debug, Clone, Resource, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub volume: Volume,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            volume: Volume {
+                effects: 1.0,
+                ambience: 0.5,
+            },
+        }
+    }
+}
+
+/// Volume of each category of sound, from 0 to 1
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Volume {
+    pub effects: f32,
+    pub ambience: f32,
+}
+
+/// Falls back to the defaults if there are no settings saved yet, or they can't be read
+pub fn load_settings(mut commands: Commands) {
+    let settings = match storage::read(SETTINGS_KEY) {
+        Some(saved) => ron::from_str(&saved).unwrap_or_else(|err| {
+            warn!("ignoring unreadable settings: {err}");
+            Settings::default()
+        }),
+        None => Settings::default(),
+    };
+    commands.insert_resource(settings);
+}
+
+pub fn save_settings(settings: Res<Settings>) {
+    if settings.is_changed() && !settings.is_added() {
+        storage::write(
+            SETTINGS_KEY,
+            &ron::ser::to_string_pretty(&*settings, default()).unwrap(),
+        );
+    }
+}
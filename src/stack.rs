@@ -0,0 +1,55 @@
+//! The pile of documents and tools on the desk, from the bottom up.
+//!
+//! Whatever's picked up goes on top of the pile. Things only ever get a z from their place in
+//! the pile, renumbered from the bottom every time the pile changes, so however often things
+//! are picked up the pile stays between the same heights and children keep layering the same
+//! way relative to their parents.
+use bevy::prelude::*;
+use ordered_float::NotNan;
+
+use crate::{DragHitBox, PickedUp};
+
+/// Bottom of the pile, above the trays
+const STACK_BASE_Z: f32 = 10.0;
+/// Top of the pile, below the newspapers landing on the desk
+const STACK_TOP_Z: f32 = 800.0;
+/// Room between things in the pile for the layers of their children
+const STACK_STEP_Z: f32 = 30.0;
+
+#[derive(Debug, Default, Resource)]
+pub struct DeskStack(Vec<Entity>);
+
+/// Keeps the pile up to date with what's on the desk, and puts picked up things on top
+pub fn restack(
+    mut picked_up: EventReader<PickedUp>,
+    added: Query<Entity, Added<DragHitBox>>,
+    mut transforms: Query<&mut Transform, With<DragHitBox>>,
+    mut stack: ResMut<DeskStack>,
+) {
+    let height = stack.0.len();
+    stack.0.retain(|&entity| transforms.contains(entity));
+    let mut changed = stack.0.len() != height;
+
+    // New things go on top, keeping the layering they were spawned with
+    let mut added: Vec<Entity> = added.iter().collect();
+    added
+        .sort_by_key(|&entity| NotNan::new(transforms.get(entity).unwrap().translation.z).unwrap());
+    changed |= !added.is_empty();
+    stack.0.extend(added);
+
+    for &PickedUp { dragged } in picked_up.iter() {
+        if transforms.contains(dragged) {
+            stack.0.retain(|&entity| entity != dragged);
+            stack.0.push(dragged);
+            changed = true;
+        }
+    }
+
+    if changed {
+        // A pile too tall to fit gets squeezed together rather than going through the top
+        let step = STACK_STEP_Z.min((STACK_TOP_Z - STACK_BASE_Z) / stack.0.len().max(1) as f32);
+        for (i, &entity) in stack.0.iter().enumerate() {
+            transforms.get_mut(entity).unwrap().translation.z = STACK_BASE_Z + step * i as f32;
+        }
+    }
+}
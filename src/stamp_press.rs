@@ -0,0 +1,65 @@
+//! Stamping is its own gesture, separate from moving a stamp around: right clicking a stamp
+//! presses it down onto whatever is under it.
+use bevy::prelude::*;
+
+use crate::{Stamp, UnderMouse};
+
+/// How long a press takes, down and back up
+const PRESS_SECONDS: f32 = 0.3;
+/// How much the stamp shrinks and squashes at the bottom of a press
+const PRESS_SQUASH: Vec2 = Vec2::new(0.1, 0.2);
+
+/// A stamp on its way down to the paper and back up
+#[derive(Component)]
+pub struct StampPress {
+    timer: Timer,
+    landed: bool,
+}
+
+/// Sent when a pressed stamp hits the paper
+pub struct StampPressed {
+    pub stamp: Entity,
+}
+
+/// Right clicking the stamp in front under the mouse presses it, whether it's being held or not
+pub fn press_stamps(
+    input: Res<Input<MouseButton>>,
+    under_mouse: UnderMouse,
+    stamps: Query<(), (With<Stamp>, Without<StampPress>)>,
+    mut commands: Commands,
+) {
+    if !input.just_pressed(MouseButton::Right) {
+        return;
+    }
+    if let Some(entity) = under_mouse.front().filter(|&front| stamps.contains(front)) {
+        commands.entity(entity).insert(StampPress {
+            timer: Timer::from_seconds(PRESS_SECONDS, TimerMode::Once),
+            landed: false,
+        });
+    }
+}
+
+pub fn animate_stamp_presses(
+    time: Res<Time>,
+    mut stamps: Query<(Entity, &mut StampPress, &mut Transform)>,
+    mut pressed: EventWriter<StampPressed>,
+    mut commands: Commands,
+) {
+    for (entity, mut press, mut transform) in &mut stamps {
+        press.timer.tick(time.delta());
+        let progress = press.timer.percent();
+
+        // All the way down halfway through, then back up
+        let depth = 1.0 - (progress * 2.0 - 1.0).abs();
+        transform.scale = (Vec2::ONE - PRESS_SQUASH * depth).extend(1.0);
+
+        if !press.landed && progress >= 0.5 {
+            press.landed = true;
+            pressed.send(StampPressed { stamp: entity });
+        }
+        if press.timer.finished() {
+            transform.scale = Vec3::ONE;
+            commands.entity(entity).remove::<StampPress>();
+        }
+    }
+}
@@ -0,0 +1,143 @@
+//! The recommendation box on a dossier, where the stamp has to go for the recommendation to
+//! count, and the telling off an analyst gets for stamping anywhere else.
+use bevy::{prelude::*, text::Text2dBounds};
+
+/// Where the recommendation goes on a dossier, relative to the paper
+#[derive(Component)]
+pub struct StampZone {
+    pub rect: Rect,
+}
+
+/// Sent when a stamp lands on a dossier outside its recommendation box
+pub struct StampMisplaced {
+    pub dossier: Entity,
+}
+
+/// The border around the recommendation box, flashing red for a while after a misplaced stamp
+#[derive(Component)]
+pub struct StampZoneOutline {
+    warning: Timer,
+}
+
+/// A sticky note from the supervisor, stuck on a dossier after a misplaced stamp
+#[derive(Component)]
+pub struct SupervisorNote;
+
+const OUTLINE_COLOR: Color = Color::rgb(0.7, 0.7, 0.7);
+const OUTLINE_WARNING_COLOR: Color = Color::rgb(0.85, 0.1, 0.1);
+const OUTLINE_WIDTH: f32 = 2.0;
+/// How long the outline stays red
+const WARNING_SECONDS: f32 = 2.0;
+
+/// Draws the recommendation box on the paper, as a border with the white paper showing
+/// through the middle
+pub fn spawn_stamp_zone_outline(paper: &mut ChildBuilder, zone: Rect) {
+    paper
+        .spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: OUTLINE_COLOR,
+                    custom_size: Some(zone.size()),
+                    ..default()
+                },
+                transform: Transform::from_translation(zone.center().extend(0.5)),
+                ..default()
+            },
+            StampZoneOutline {
+                warning: Timer::from_seconds(WARNING_SECONDS, TimerMode::Once),
+            },
+        ))
+        .with_children(|outline| {
+            outline.spawn(SpriteBundle {
+                sprite: Sprite {
+                    color: Color::WHITE,
+                    custom_size: Some(zone.size() - Vec2::splat(OUTLINE_WIDTH * 2.0)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(0.0, 0.0, 0.1),
+                ..default()
+            });
+        });
+}
+
+pub fn warn_misplaced_stamps(
+    mut misplaced: EventReader<StampMisplaced>,
+    children: Query<&Children>,
+    mut outlines: Query<(&mut StampZoneOutline, &mut Sprite)>,
+    notes: Query<(), With<SupervisorNote>>,
+    dossiers: Query<&StampZone>,
+    asset_server: Res<AssetServer>,
+    mut commands: Commands,
+) {
+    for &StampMisplaced { dossier } in misplaced.iter() {
+        let Ok(zone) = dossiers.get(dossier) else {
+            continue;
+        };
+        let parts = children
+            .get(dossier)
+            .map(|children| &children[..])
+            .unwrap_or(&[]);
+        for &part in parts {
+            if let Ok((mut outline, mut sprite)) = outlines.get_mut(part) {
+                outline.warning.reset();
+                sprite.color = OUTLINE_WARNING_COLOR;
+            }
+        }
+
+        // One note is enough to get the message across
+        if parts.iter().any(|&part| notes.contains(part)) {
+            continue;
+        }
+        let note_size = Vec2::new(160.0, 70.0);
+        commands.entity(dossier).with_children(|paper| {
+            paper
+                .spawn((
+                    SpriteBundle {
+                        sprite: Sprite {
+                            color: Color::rgb(1.0, 0.95, 0.5),
+                            custom_size: Some(note_size),
+                            ..default()
+                        },
+                        // Stuck just above the box, over whatever is printed there
+                        transform: Transform::from_translation(
+                            Vec2::new(zone.rect.max.x - note_size.x / 2.0, zone.rect.max.y)
+                                .extend(25.0),
+                        )
+                        .with_rotation(Quat::from_rotation_z(-0.05)),
+                        ..default()
+                    },
+                    SupervisorNote,
+                ))
+                .with_children(|note| {
+                    note.spawn(Text2dBundle {
+                        text: Text::from_section(
+                            "Stamp INSIDE the recommendation box!\n- Supervisor",
+                            TextStyle {
+                                font: asset_server.load("fonts/FiraMono-Medium.ttf"),
+                                font_size: 12.0,
+                                color: Color::rgb(0.1, 0.1, 0.4),
+                            },
+                        )
+                        .with_alignment(TextAlignment::CENTER),
+                        text_2d_bounds: Text2dBounds {
+                            size: note_size - Vec2::splat(8.0),
+                        },
+                        transform: Transform::from_xyz(0.0, 0.0, 0.1),
+                        ..default()
+                    });
+                });
+        });
+    }
+}
+
+/// Turns the outline back once the warning has run its course
+pub fn fade_stamp_zone_warnings(
+    time: Res<Time>,
+    mut outlines: Query<(&mut StampZoneOutline, &mut Sprite)>,
+) {
+    for (mut outline, mut sprite) in &mut outlines {
+        if outline.warning.tick(time.delta()).just_finished() {
+            sprite.color = OUTLINE_COLOR;
+        }
+    }
+}
@@ -0,0 +1,67 @@
+//! Somewhere to keep things between runs: files next to the game natively, and the browser's
+//! `localStorage` on the web.
+
+#[cfg(not(target_arch = "wasm32"))]
+mod backend {
+    use std::{fs, io::ErrorKind, path::PathBuf};
+
+    use bevy::prelude::*;
+
+    fn path(key: &str) -> PathBuf {
+        PathBuf::from(format!("{key}.ron"))
+    }
+
+    pub fn read(key: &str) -> Option<String> {
+        match fs::read_to_string(path(key)) {
+            Ok(value) => Some(value),
+            Err(err) if err.kind() == ErrorKind::NotFound => None,
+            Err(err) => {
+                warn!("couldn't read {}: {err}", path(key).display());
+                None
+            }
+        }
+    }
+
+    pub fn write(key: &str, value: &str) {
+        if let Err(err) = fs::write(path(key), value) {
+            warn!("couldn't write {}: {err}", path(key).display());
+        }
+    }
+
+    pub fn remove(key: &str) {
+        match fs::remove_file(path(key)) {
+            Ok(()) => {}
+            Err(err) if err.kind() == ErrorKind::NotFound => {}
+            Err(err) => warn!("couldn't remove {}: {err}", path(key).display()),
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod backend {
+    use bevy::prelude::*;
+    use web_sys::Storage;
+
+    fn local_storage() -> Option<Storage> {
+        web_sys::window()?.local_storage().ok()?
+    }
+
+    pub fn read(key: &str) -> Option<String> {
+        local_storage()?.get_item(key).ok()?
+    }
+
+    pub fn write(key: &str, value: &str) {
+        let written = local_storage().map(|storage| storage.set_item(key, value).is_ok());
+        if written != Some(true) {
+            warn!("couldn't write {key} to local storage");
+        }
+    }
+
+    pub fn remove(key: &str) {
+        if let Some(storage) = local_storage() {
+            let _ = storage.remove_item(key);
+        }
+    }
+}
+
+pub use backend::{read, remove, write};
@@ -0,0 +1,98 @@
+//! Fading the screen to black and back between game states.
+//!
+//! Systems send a [`RequestTransition`] instead of switching state themselves. The screen fades
+//! out, the state changes behind the black, then it fades back in.
+use bevy::{prelude::*, ui::FocusPolicy};
+use iyes_loopless::state::NextState;
+
+use crate::GameState;
+
+/// How long each half of a transition takes
+const FADE_SECONDS: f32 = 0.4;
+
+/// Sent to move to another state, fading through black
+pub struct RequestTransition(pub GameState);
+
+#[derive(Debug, Default, Resource)]
+pub enum Transition {
+    #[default]
+    Idle,
+    FadingOut(GameState, Timer),
+    FadingIn(Timer),
+}
+
+/// Whether a transition is underway, for systems that shouldn't act on input meanwhile
+pub fn transitioning(transition: Res<Transition>) -> bool {
+    !matches!(*transition, Transition::Idle)
+}
+
+/// Black over everything, only shown during a transition
+#[derive(Component)]
+pub struct FadeOverlay;
+
+pub fn spawn_fade_overlay(mut commands: Commands) {
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                position_type: PositionType::Absolute,
+                ..default()
+            },
+            background_color: Color::NONE.into(),
+            // Nothing underneath can be clicked while fading
+            focus_policy: FocusPolicy::Block,
+            visibility: Visibility { is_visible: false },
+            z_index: ZIndex::Global(i32::MAX),
+            ..default()
+        },
+        FadeOverlay,
+    ));
+}
+
+pub fn start_transitions(
+    mut requests: EventReader<RequestTransition>,
+    mut transition: ResMut<Transition>,
+) {
+    // Anything asked for while already on the way somewhere is dropped
+    for &RequestTransition(to) in requests.iter() {
+        if let Transition::Idle = *transition {
+            *transition =
+                Transition::FadingOut(to, Timer::from_seconds(FADE_SECONDS, TimerMode::Once));
+        }
+    }
+}
+
+pub fn fade(
+    time: Res<Time>,
+    mut transition: ResMut<Transition>,
+    mut overlay: Query<(&mut BackgroundColor, &mut Visibility), With<FadeOverlay>>,
+    mut commands: Commands,
+) {
+    let (mut color, mut visibility) = overlay.single_mut();
+    let alpha = match &mut *transition {
+        Transition::Idle => {
+            visibility.is_visible = false;
+            return;
+        }
+        Transition::FadingOut(to, timer) => {
+            if timer.tick(time.delta()).just_finished() {
+                // The state changes while the screen is black
+                commands.insert_resource(NextState(*to));
+                *transition =
+                    Transition::FadingIn(Timer::from_seconds(FADE_SECONDS, TimerMode::Once));
+                1.0
+            } else {
+                timer.percent()
+            }
+        }
+        Transition::FadingIn(timer) => {
+            let alpha = timer.tick(time.delta()).percent_left();
+            if timer.finished() {
+                *transition = Transition::Idle;
+            }
+            alpha
+        }
+    };
+    visibility.is_visible = true;
+    *color = Color::rgba(0.0, 0.0, 0.0, alpha).into();
+}